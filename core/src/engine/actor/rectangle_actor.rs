@@ -2,7 +2,7 @@ use crate::engine::{
     color::Color,
     color_matrix::ColorMatrix,
     components::{
-        collider::{Collider, ColliderPart, ColliderType},
+        collider::{Collider, ColliderPart, ColliderShape, ColliderType},
         transform::Transform,
         world::World,
     },
@@ -15,10 +15,10 @@ pub fn create_rectangle_actor(world: &mut World, center: V2, size: V2, color: Co
         name.or_else(|| Some(String::from("rectangle"))),
         Some(Transform::new(center, size.clone())),
         if let Some(col_type) = collider_type {
-            Some(Collider::new(vec![ColliderPart { offset: V2::zero(), extend: size.clone(), is_overlap: col_type == ColliderType::Blocking }], Some(0)))
+            Some(Collider::new(vec![ColliderPart { offset: V2::zero(), shape: ColliderShape::Box { extend: size.clone() }, collider_type: col_type }], Some(0)))
         }
         else {None},
         None,
-        Some(ColorMatrix::new(size.x as u8, size.y as u8, color)),
+        Some(ColorMatrix::new(size.x as usize, size.y as usize, color)),
     )
 }
@@ -1,12 +1,6 @@
-use std::{
-    sync::{
-        OnceLock, RwLock,
-        mpsc::{Receiver, Sender, channel},
-    },
-    u16,
-};
+use std::sync::{Arc, Mutex, RwLock};
 
-use crate::engine::components::world::World;
+use crate::engine::{components::world::World, scene::SceneId};
 
 pub type AsyncableId = u16;
 pub type AsyncableFunction = Box<dyn FnMut(&mut World, f32) + Send + Sync + 'static>;
@@ -19,12 +13,6 @@ pub enum AsyncableActionType {
     Remove,
 }
 
-static QUEUE: OnceLock<Sender<EnqueuedAsyncableJob>> = OnceLock::new();
-static TAKEN_IDS: OnceLock<RwLock<Vec<AsyncableId>>> = OnceLock::new();
-fn get_taken_ids() -> &'static RwLock<Vec<AsyncableId>> {
-    TAKEN_IDS.get_or_init(|| RwLock::new(Vec::new()))
-}
-
 pub struct EnqueuedAsyncableJob {
     pub id_to_affect: AsyncableId,
     pub action_type: AsyncableActionType,
@@ -33,29 +21,92 @@ pub struct EnqueuedAsyncableJob {
 
 pub struct AsyncableInProgress {
     pub id: AsyncableId,
+    pub scene_id: SceneId,
     pub function: AsyncableFunction,
     pub async_type: AsyncableType,
     pub ms: f32,
     pub timer: f32,
 }
 
+#[derive(Clone, Default)]
+pub struct AsyncableQueue {
+    jobs: Arc<Mutex<Vec<EnqueuedAsyncableJob>>>,
+    taken_ids: Arc<RwLock<Vec<AsyncableId>>>,
+}
+
+impl AsyncableQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&self, scene_id: SceneId, function: AsyncableFunction, ms: f32, asyncable_type: AsyncableType) -> AsyncableId {
+        let mut taken_ids = self.taken_ids.write().unwrap();
+        let mut free_id = 0;
+        for i in 0..=u16::MAX {
+            // TODO optimize this shit
+            free_id = i;
+            if !taken_ids.contains(&free_id) {
+                break;
+            }
+        }
+
+        taken_ids.push(free_id);
+        taken_ids.sort();
+        drop(taken_ids);
+
+        self.jobs.lock().unwrap().push(EnqueuedAsyncableJob {
+            id_to_affect: free_id,
+            action_type: AsyncableActionType::Add,
+            asyncable: Some(AsyncableInProgress {
+                id: free_id,
+                scene_id,
+                function,
+                async_type: asyncable_type,
+                ms,
+                timer: 0.0,
+            }),
+        });
+
+        free_id
+    }
+
+    pub fn remove(&self, id: AsyncableId) {
+        self.taken_ids.write().unwrap().retain(|f| f != &id);
+        self.jobs.lock().unwrap().push(EnqueuedAsyncableJob {
+            id_to_affect: id,
+            action_type: AsyncableActionType::Remove,
+            asyncable: None,
+        });
+    }
+
+    fn take_jobs(&self) -> Vec<EnqueuedAsyncableJob> {
+        std::mem::take(&mut *self.jobs.lock().unwrap())
+    }
+
+    fn free_ids(&self, ids: &[AsyncableId]) {
+        self.taken_ids.write().unwrap().retain(|f| !ids.contains(f));
+    }
+}
+
 pub struct AsyncableStorage {
     asyncables_in_progress: Vec<AsyncableInProgress>,
-    queue_receiver: Receiver<EnqueuedAsyncableJob>,
+    queue: AsyncableQueue,
 }
 
 impl AsyncableStorage {
     pub fn new() -> Self {
-        let (s, r) = channel();
-        QUEUE.set(s).unwrap();
         Self {
             asyncables_in_progress: Vec::new(),
-            queue_receiver: r,
+            queue: AsyncableQueue::new(),
         }
     }
 
-    pub fn update(&mut self, world: &mut World, delta_time: f32) {
-        while let Ok(job) = self.queue_receiver.try_recv() {
+    pub fn get_queue(&self) -> AsyncableQueue {
+        self.queue.clone()
+    }
+
+    fn apply_queued_jobs(&mut self) {
+        for job in self.queue.take_jobs() {
             match job.action_type {
                 AsyncableActionType::Add => {
                     self.asyncables_in_progress.push(job.asyncable.unwrap());
@@ -65,15 +116,19 @@ impl AsyncableStorage {
                 }
             }
         }
+    }
+
+    pub fn update(&mut self, scene_id: SceneId, world: &mut World, delta_time: f32) {
+        self.apply_queued_jobs();
 
-        for asyncable in self.asyncables_in_progress.iter_mut() {
+        for asyncable in self.asyncables_in_progress.iter_mut().filter(|f| f.scene_id == scene_id) {
             asyncable.timer += delta_time;
 
             match asyncable.async_type {
                 AsyncableType::Timeout => {
                     if asyncable.timer >= asyncable.ms {
                         (asyncable.function)(world, delta_time);
-                        remove_asyncable(asyncable.id);
+                        self.queue.remove(asyncable.id);
                     }
                 }
                 AsyncableType::Interval => {
@@ -85,47 +140,17 @@ impl AsyncableStorage {
             }
         }
     }
-}
 
-pub fn add_asyncable(function: AsyncableFunction, ms: f32, asyncable_type: AsyncableType) -> AsyncableId {
-    let lock = get_taken_ids().read().unwrap();
-    let mut free_id = 0;
-    for i in 0..=u16::MAX {
-        // TODO optimize this shit
-        free_id = i;
-        if !lock.contains(&free_id) {
-            break;
-        }
+    pub fn remove_scene_asyncables(&mut self, scene_id: SceneId) {
+        self.apply_queued_jobs();
+        let removed: Vec<AsyncableId> = self.asyncables_in_progress.iter().filter(|f| f.scene_id == scene_id).map(|f| f.id).collect();
+        self.asyncables_in_progress.retain(|f| f.scene_id != scene_id);
+        self.queue.free_ids(&removed);
     }
-
-    let job = EnqueuedAsyncableJob {
-        id_to_affect: free_id.clone(),
-        action_type: AsyncableActionType::Add,
-        asyncable: Some(AsyncableInProgress {
-            id: free_id.clone(),
-            function,
-            async_type: asyncable_type,
-            ms,
-            timer: 0.0,
-        }),
-    };
-
-    drop(lock);
-
-    get_taken_ids().write().unwrap().push(free_id.clone());
-    get_taken_ids().write().unwrap().sort();
-    QUEUE.get().unwrap().send(job).unwrap();
-
-    free_id
 }
 
-pub fn remove_asyncable(id: AsyncableId) {
-    let job = EnqueuedAsyncableJob {
-        id_to_affect: id.clone(),
-        action_type: AsyncableActionType::Remove,
-        asyncable: None,
-    };
-
-    get_taken_ids().write().unwrap().retain_mut(|f| f != &id);
-    QUEUE.get().unwrap().send(job).unwrap();
+impl Default for AsyncableStorage {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -1,6 +1,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-#[derive(PartialEq, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Clone, Serialize, Deserialize)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
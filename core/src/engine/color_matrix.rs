@@ -6,20 +6,7 @@ pub type ColorMatrix = Matrix<Color>;
 
 impl Matrix<Color> {
     pub fn write_at_origin(&mut self, other: &ColorMatrix, origin: &V2) -> &ColorMatrix {
-        if origin.x < other.width as f32 && origin.y < other.height as f32 {
-            for x in 0..other.width {
-                for y in 0..other.height {
-                    let tx = x + origin.x as u8;
-                    let ty = y + origin.y as u8;
-
-                    if tx < self.width && ty < self.height {
-                        self.set(x, y, other.get(x, y).clone());
-                    }
-                }
-            }
-        }
-
-        self
+        self.blit(other, origin.x.floor() as i32, origin.y.floor() as i32)
     }
 
     pub fn write(
@@ -90,7 +77,7 @@ impl Matrix<Color> {
                     && final_x < self.width as f32
                     && final_y < self.height as f32
                 {
-                    let dst = self.get(final_x as u8, final_y as u8).clone();
+                    let dst = self.get(final_x as usize, final_y as usize).clone();
 
                     if blend_colors {
                         let out_a: i16 = src.a as i16 + dst.a as i16 + (255 - src.a) as i16;
@@ -112,8 +99,8 @@ impl Matrix<Color> {
                             / out_a as f32;
 
                         self.set(
-                            final_x as u8,
-                            final_y as u8,
+                            final_x as usize,
+                            final_y as usize,
                             Color::new(
                                 r.clamp(0.0, 255.0) as u8,
                                 g.clamp(0.0, 255.0) as u8,
@@ -122,7 +109,7 @@ impl Matrix<Color> {
                             ),
                         );
                     } else {
-                        self.set(final_x as u8, final_y as u8, src.clone());
+                        self.set(final_x as usize, final_y as usize, src.clone());
                     }
                 }
             }
@@ -0,0 +1,77 @@
+use crate::engine::{
+    color_matrix::ColorMatrix,
+    components::{collider::Collider, physics::Physics, transform::Transform, world::World},
+    engine::ActorId,
+};
+
+pub type Command = Box<dyn FnOnce(&mut World) + Send + 'static>;
+
+pub struct Commands {
+    queue: Vec<Command>,
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Self { queue: Vec::new() }
+    }
+
+    pub fn add<F>(&mut self, command: F)
+    where
+        F: FnOnce(&mut World) + Send + 'static,
+    {
+        self.queue.push(Box::new(command));
+    }
+
+    pub fn spawn(
+        &mut self,
+        name: Option<String>,
+        transform: Option<Transform>,
+        collider: Option<Collider>,
+        physics: Option<Physics>,
+        render: Option<ColorMatrix>,
+    ) {
+        self.add(move |world| {
+            world.add_new_actor(name, transform, collider, physics, render);
+        });
+    }
+
+    pub fn spawn_with<F>(&mut self, name: Option<String>, on_spawned: F)
+    where
+        F: FnOnce(&mut World, ActorId) + Send + 'static,
+    {
+        self.add(move |world| {
+            let actor_id = world.add_new_actor(name, None, None, None, None);
+            on_spawned(world, actor_id);
+        });
+    }
+
+    pub fn despawn(&mut self, actor_id: ActorId) {
+        self.add(move |world| world.remove_actor(&actor_id));
+    }
+
+    pub fn insert<T: Send + 'static>(&mut self, actor_id: ActorId, component: T) {
+        self.add(move |world| {
+            world.insert(&actor_id, component);
+        });
+    }
+
+    pub fn remove<T: 'static>(&mut self, actor_id: ActorId) {
+        self.add(move |world| {
+            world.remove::<T>(&actor_id);
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn take(&mut self) -> Vec<Command> {
+        std::mem::take(&mut self.queue)
+    }
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Self::new()
+    }
+}
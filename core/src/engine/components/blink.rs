@@ -0,0 +1,18 @@
+use crate::engine::color_matrix::ColorMatrix;
+
+#[derive(Clone)]
+pub struct Blink {
+    pub interval: f32,
+    pub timer: f32,
+    pub hidden_render: Option<ColorMatrix>,
+}
+
+impl Blink {
+    pub fn new(interval: f32) -> Self {
+        Self {
+            interval,
+            timer: 0.0,
+            hidden_render: None,
+        }
+    }
+}
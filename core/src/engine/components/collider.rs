@@ -1,7 +1,14 @@
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, u8};
 
 use crate::engine::{
-    components::{transform::Transform, world::World},
+    components::{
+        contact::Contact,
+        physics::Physics,
+        sat::{self, ConvexShape, Penetration},
+        transform::Transform,
+        world::World,
+    },
     engine::ActorId,
     v2::V2,
 };
@@ -9,18 +16,72 @@ use crate::engine::{
 pub type CollisionMask = u8;
 pub type CollisionMaskId = u8;
 
-#[derive(PartialEq, Eq)]
+const BROAD_PHASE_CELL_SIZE: f32 = 8.0;
+const BROAD_PHASE_MAX_CELLS: i32 = 64;
+const BROAD_PHASE_MARGIN: f32 = 0.01;
+
+#[derive(PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum ColliderType {
     Blocking,
     Overlapping,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+pub enum ColliderShape {
+    Box { extend: V2 },
+    Circle { radius: f32 },
+    Capsule { half_length: f32, radius: f32 },
+    /// Vertices are local to the part and must form a convex polygon; concave input gives wrong contacts.
+    ConvexPolygon { vertices: Vec<V2> },
+}
+
+impl ColliderShape {
+    fn get_bounding_radius(&self, scale: &V2) -> f32 {
+        let radius_scale = f32::max(scale.x.abs(), scale.y.abs());
+        match self {
+            ColliderShape::Box { extend } => (extend * scale).mag() / 2.0,
+            ColliderShape::Circle { radius } => radius * radius_scale,
+            ColliderShape::Capsule { half_length, radius } => (half_length * scale.x).abs() + radius * radius_scale,
+            ColliderShape::ConvexPolygon { vertices } => vertices.iter().map(|f| (f * scale).mag()).fold(0.0, f32::max),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ColliderPart {
     pub offset: V2,
-    pub extend: V2,
-    pub is_overlap: bool,
+    pub shape: ColliderShape,
+    pub collider_type: ColliderType,
 }
 
+impl ColliderPart {
+    pub fn get_convex_shape(&self, transform: &Transform) -> ConvexShape {
+        let scale = transform.get_scale();
+        let radius_scale = f32::max(scale.x.abs(), scale.y.abs());
+        let center = &transform.center + &(&self.offset * &scale).rotated(transform.rotation);
+        let to_world = |local: V2| &center + &(&local * &scale).rotated(transform.rotation);
+
+        match &self.shape {
+            ColliderShape::Box { extend } => ConvexShape::new(
+                vec![
+                    to_world(V2::new(-extend.x / 2.0, -extend.y / 2.0)),
+                    to_world(V2::new(extend.x / 2.0, -extend.y / 2.0)),
+                    to_world(V2::new(extend.x / 2.0, extend.y / 2.0)),
+                    to_world(V2::new(-extend.x / 2.0, extend.y / 2.0)),
+                ],
+                0.0,
+            ),
+            ColliderShape::Circle { radius } => ConvexShape::new(vec![center.clone()], radius * radius_scale),
+            ColliderShape::Capsule { half_length, radius } => ConvexShape::new(
+                vec![to_world(V2::new(-half_length, 0.0)), to_world(V2::new(*half_length, 0.0))],
+                radius * radius_scale,
+            ),
+            ColliderShape::ConvexPolygon { vertices } => ConvexShape::new(vertices.iter().map(|f| to_world(f.clone())).collect(), 0.0),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Collider {
     pub collider_parts: Vec<ColliderPart>,
     pub mask_id: CollisionMaskId,
@@ -34,116 +95,394 @@ impl Collider {
         }
     }
 
-    pub fn detect_overlaps(world: &World) -> HashMap<u16, Vec<u16>> {
+    pub fn detect_overlaps(world: &World) -> HashMap<ActorId, Vec<ActorId>> {
         let mut dict = HashMap::<ActorId, Vec<ActorId>>::new();
-        for first_actor in &world.all_actors {
-            for i in (first_actor.clone() as usize + 1)..world.all_actors.len() {
-                let second_actor = &world.all_actors[i];
-
-                if first_actor != second_actor
-                    && let Some(first_collider) = world.get_collider(first_actor)
-                    && let Some(first_transform) = world.get_transform(first_actor)
-                    && let Some(second_collider) = world.get_collider(second_actor)
-                    && let Some(second_transform) = world.get_transform(second_actor)
-                    && (!dict.contains_key(first_actor) || !dict[first_actor].contains(second_actor))
-                {
-                    if (world.get_collision_matrix(first_collider.mask_id) & 1 << second_collider.mask_id) == 1
-                        && Collider::is_overlapping((first_collider, first_transform), (second_collider, second_transform))
-                    {
-                        if !dict.contains_key(first_actor) {
-                            dict.insert(first_actor.clone(), Vec::new());
-                        }
-
-                        if !dict.contains_key(second_actor) {
-                            dict.insert(second_actor.clone(), Vec::new());
-                        }
-
-                        dict.get_mut(first_actor).unwrap().push(second_actor.clone());
-                        dict.get_mut(second_actor).unwrap().push(first_actor.clone());
-                    }
-                }
+        let actors = world.query_ref::<(Collider, Transform)>();
+        for (first_index, second_index) in Collider::find_candidate_pairs(&actors) {
+            let (first_actor, (first_collider, first_transform)) = &actors[first_index];
+            let (second_actor, (second_collider, second_transform)) = &actors[second_index];
+            if world.are_masks_colliding(first_collider.mask_id, second_collider.mask_id)
+                && Collider::is_overlapping((first_collider, first_transform), (second_collider, second_transform))
+            {
+                dict.entry(*first_actor).or_default().push(*second_actor);
+                dict.entry(*second_actor).or_default().push(*first_actor);
             }
         }
 
         dict
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.collider_parts.is_empty()
+    pub fn resolve_blocking(world: &mut World) -> Vec<Contact> {
+        let pairs = {
+            let actors = world.query_ref::<(Collider, Transform)>();
+            Collider::find_candidate_pairs(&actors)
+                .into_iter()
+                .filter(|(first, second)| world.are_masks_colliding(actors[*first].1.0.mask_id, actors[*second].1.0.mask_id))
+                .map(|(first, second)| (actors[first].0, actors[second].0))
+                .collect::<Vec<_>>()
+        };
+
+        let mut contacts = Vec::new();
+        for (first, second) in pairs {
+            let (first_root, second_root) = (world.get_root(&first), world.get_root(&second));
+            if first_root == second_root {
+                continue;
+            }
+
+            let first_share = if world.has::<Physics>(&first_root) { 1.0 } else { 0.0 };
+            let second_share = if world.has::<Physics>(&second_root) { 1.0 } else { 0.0 };
+            if first_share + second_share == 0.0 {
+                continue;
+            }
+
+            let (Some(first_collider), Some(first_transform), Some(second_collider), Some(second_transform)) = (
+                world.get_collider(&first),
+                world.get_transform(&first),
+                world.get_collider(&second),
+                world.get_transform(&second),
+            ) else {
+                continue;
+            };
+            let Some(Penetration { normal, depth }) = Collider::get_penetration((first_collider, first_transform), (second_collider, second_transform)) else {
+                continue;
+            };
+
+            let total_share = first_share + second_share;
+            Collider::push_out(world, &first_root, &(&normal * -1.0), depth * first_share / total_share);
+            Collider::push_out(world, &second_root, &normal, depth * second_share / total_share);
+            contacts.push(Contact { first, second, normal, depth });
+        }
+
+        contacts
     }
 
-    fn get_parts_vertices(part: &ColliderPart) -> [V2; 4] {
-        [
-            V2::new(part.extend.x / 2.0, part.extend.y / 2.0),
-            V2::new(part.extend.x / 2.0, -part.extend.y / 2.0),
-            V2::new(-part.extend.x / 2.0, -part.extend.y / 2.0),
-            V2::new(-part.extend.x / 2.0, part.extend.y / 2.0),
-        ]
+    fn push_out(world: &mut World, actor_id: &ActorId, direction: &V2, distance: f32) {
+        if distance <= 0.0 {
+            return;
+        }
+
+        Collider::move_with_children(world, actor_id, &(direction * distance));
+        if let Some(physics) = world.get_mut_physics(actor_id) {
+            let speed_into = physics.velocity.dot(direction);
+            if speed_into < 0.0 {
+                physics.velocity -= direction * speed_into;
+            }
+        }
     }
 
-    pub fn is_overlapping(first: (&Collider, &Transform), second: (&Collider, &Transform)) -> bool {
-        fn ccw(a: &V2, b: &V2, c: &V2) -> bool {
-            (c.y - a.y) * (b.x - a.x) > (b.y - a.y) * (c.x - a.x)
+    fn move_with_children(world: &mut World, actor_id: &ActorId, offset: &V2) {
+        if let Some(transform) = world.get_mut_transform(actor_id) {
+            transform.center += offset.clone();
+        }
+        for child in world.get_children(actor_id) {
+            Collider::move_with_children(world, &child, offset);
         }
+    }
 
-        fn do_intersect(a: &V2, b: &V2, c: &V2, d: &V2) -> bool {
-            ccw(&a, &c, &d) != ccw(&b, &c, &d) && ccw(&a, &b, &c) != ccw(&a, &b, &d)
+    pub fn get_penetration(first: (&Collider, &Transform), second: (&Collider, &Transform)) -> Option<Penetration> {
+        let mut deepest: Option<Penetration> = None;
+        for first_part in first.0.collider_parts.iter().filter(|f| f.collider_type == ColliderType::Blocking) {
+            for second_part in second.0.collider_parts.iter().filter(|f| f.collider_type == ColliderType::Blocking) {
+                let Some(penetration) = sat::test_shapes(&first_part.get_convex_shape(first.1), &second_part.get_convex_shape(second.1)) else {
+                    continue;
+                };
+                if deepest.as_ref().is_none_or(|f| f.depth < penetration.depth) {
+                    deepest = Some(penetration);
+                }
+            }
         }
 
-        fn is_part_overlapping(first_part: &ColliderPart, first_center: &V2, second_part: &ColliderPart, second_center: &V2) -> bool {
-            let first_vertices = Collider::get_parts_vertices(first_part).map(|x| &x + first_center);
-            let second_vertices = Collider::get_parts_vertices(second_part).map(|x| &x + second_center);
+        deepest
+    }
 
-            let min_first_vertex = &first_vertices.iter().reduce(|a, b| if a.x < b.x { a } else { b }).unwrap();
-            for s_v in second_vertices {
-                let mut intersects: u8 = 0;
+    fn find_candidate_pairs(actors: &[(ActorId, (&Collider, &Transform))]) -> Vec<(usize, usize)> {
+        let mut grid = HashMap::<(i32, i32), Vec<usize>>::new();
+        let mut oversized = Vec::<usize>::new();
+        let mut pairs = Vec::<(usize, usize)>::new();
 
-                for i in 0..first_vertices.len() {
-                    let j = (i + 1) % &first_vertices.len();
+        for (index, (_, (collider, transform))) in actors.iter().enumerate() {
+            if collider.is_empty() {
+                continue;
+            }
 
-                    if do_intersect(&V2::new(min_first_vertex.x - 1.0, s_v.y), &s_v, &first_vertices[i], &first_vertices[j]) {
-                        intersects += 1;
-                    }
-                }
+            let reach = collider.get_bounding_radius(transform) + BROAD_PHASE_MARGIN;
+            let min_x = ((transform.center.x - reach) / BROAD_PHASE_CELL_SIZE).floor() as i32;
+            let max_x = ((transform.center.x + reach) / BROAD_PHASE_CELL_SIZE).floor() as i32;
+            let min_y = ((transform.center.y - reach) / BROAD_PHASE_CELL_SIZE).floor() as i32;
+            let max_y = ((transform.center.y + reach) / BROAD_PHASE_CELL_SIZE).floor() as i32;
+
+            if (max_x - min_x + 1) * (max_y - min_y + 1) > BROAD_PHASE_MAX_CELLS {
+                oversized.push(index);
+                continue;
+            }
 
-                if intersects > 0 && intersects % 2 == 1 {
-                    return true;
+            for x in min_x..=max_x {
+                for y in min_y..=max_y {
+                    grid.entry((x, y)).or_default().push(index);
                 }
             }
+        }
 
-            false
+        for cell in grid.values() {
+            for (position, first_index) in cell.iter().enumerate() {
+                for second_index in cell.iter().skip(position + 1) {
+                    pairs.push((*first_index, *second_index));
+                }
+            }
         }
 
-        if !Collider::are_in_colliding_distance(first.0, &first.1.center, second.0, &second.1.center) {
-            return false;
+        for oversized_index in oversized {
+            for (index, (_, (collider, _))) in actors.iter().enumerate() {
+                if index != oversized_index && !collider.is_empty() {
+                    pairs.push((usize::min(index, oversized_index), usize::max(index, oversized_index)));
+                }
+            }
         }
 
-        for first_part in &first.0.collider_parts {
-            for second_part in &second.0.collider_parts {
-                if is_part_overlapping(&first_part, &first.1.center, &second_part, &second.1.center) {
-                    return true;
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    pub fn detect_overlaps_brute_force(world: &World) -> HashMap<ActorId, Vec<ActorId>> {
+        let mut dict = HashMap::<ActorId, Vec<ActorId>>::new();
+        let actors = world.query_ref::<(Collider, Transform)>();
+        for (first_index, (first_actor, (first_collider, first_transform))) in actors.iter().enumerate() {
+            for (second_actor, (second_collider, second_transform)) in actors.iter().skip(first_index + 1) {
+                if world.are_masks_colliding(first_collider.mask_id, second_collider.mask_id)
+                    && Collider::is_overlapping((first_collider, first_transform), (second_collider, second_transform))
+                {
+                    dict.entry(*first_actor).or_default().push(*second_actor);
+                    dict.entry(*second_actor).or_default().push(*first_actor);
                 }
             }
         }
 
-        false
+        dict
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.collider_parts.is_empty()
     }
 
-    pub fn are_in_colliding_distance(first_collider: &Collider, first_position: &V2, second_collider: &Collider, second_position: &V2) -> bool {
-        fn get_reach(collider: &Collider) -> f32 {
-            collider
+    pub fn is_overlapping(first: (&Collider, &Transform), second: (&Collider, &Transform)) -> bool {
+        if !Collider::are_in_colliding_distance(first, second) {
+            return false;
+        }
+
+        first.0.collider_parts.iter().any(|first_part| {
+            let first_shape = first_part.get_convex_shape(first.1);
+            second
+                .0
                 .collider_parts
                 .iter()
-                .flat_map(|f| Collider::get_parts_vertices(f).map(|g| &g + &f.offset))
-                .map(|f| f.mag())
-                .reduce(|f, g| if f < g { f } else { g })
-                .unwrap()
-        }
+                .any(|second_part| sat::test_shapes(&first_shape, &second_part.get_convex_shape(second.1)).is_some())
+        })
+    }
 
-        if first_collider.is_empty() || second_collider.is_empty() {
+    pub fn are_in_colliding_distance(first: (&Collider, &Transform), second: (&Collider, &Transform)) -> bool {
+        if first.0.is_empty() || second.0.is_empty() {
             return false;
         }
 
-        first_position.distance(second_position) < get_reach(first_collider) + get_reach(second_collider)
+        first.1.center.distance(&second.1.center) < first.0.get_bounding_radius(first.1) + second.0.get_bounding_radius(second.1)
+    }
+
+    fn get_bounding_radius(&self, transform: &Transform) -> f32 {
+        let scale = transform.get_scale();
+        self.collider_parts
+            .iter()
+            .map(|f| (&f.offset * &scale).mag() + f.shape.get_bounding_radius(&scale))
+            .fold(0.0, f32::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use crate::engine::{
+        components::{
+            collider::{Collider, ColliderPart, ColliderShape, ColliderType, CollisionMaskId},
+            physics::Physics,
+            transform::Transform,
+            world::World,
+        },
+        engine::ActorId,
+        v2::V2,
+    };
+
+    fn create_random_world(seed: u64, count: usize) -> World {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut world = World::new(64, 64);
+        for _ in 0..count {
+            let shape = if rng.gen_bool(0.5) {
+                ColliderShape::Box {
+                    extend: V2::new(rng.gen_range(0.5..6.0), rng.gen_range(0.5..6.0)),
+                }
+            } else {
+                ColliderShape::Circle { radius: rng.gen_range(0.25..3.0) }
+            };
+            let mut transform = Transform::new(V2::new(rng.gen_range(-8.0..72.0), rng.gen_range(-8.0..72.0)), V2::one());
+            transform.rotation = rng.gen_range(0.0..360.0);
+            let collider = Collider::new(
+                vec![ColliderPart {
+                    offset: V2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)),
+                    shape,
+                    collider_type: ColliderType::Overlapping,
+                }],
+                Some(rng.gen_range(0..3)),
+            );
+            world.add_new_actor(None, Some(transform), Some(collider), None, None);
+        }
+        world.add_new_actor(
+            None,
+            Some(Transform::new(V2::new(32.0, -40.0), V2::one())),
+            Some(Collider::new(
+                vec![ColliderPart {
+                    offset: V2::zero(),
+                    shape: ColliderShape::Box { extend: V2::new(200.0, 90.0) },
+                    collider_type: ColliderType::Overlapping,
+                }],
+                None,
+            )),
+            None,
+            None,
+        );
+        world
+    }
+
+    fn add_box(world: &mut World, center: V2, mask_id: CollisionMaskId) -> ActorId {
+        world.add_new_actor(
+            None,
+            Some(Transform::new(center, V2::one())),
+            Some(Collider::new(
+                vec![ColliderPart {
+                    offset: V2::zero(),
+                    shape: ColliderShape::Box { extend: V2::new(2.0, 2.0) },
+                    collider_type: ColliderType::Overlapping,
+                }],
+                Some(mask_id),
+            )),
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn collision_matrix_filters_masks() {
+        let mut world = World::new(64, 64);
+        let first = add_box(&mut world, V2::new(10.0, 10.0), 1);
+        let second = add_box(&mut world, V2::new(11.0, 10.0), 2);
+        assert_eq!(Collider::detect_overlaps(&world).get(&first), Some(&vec![second]));
+
+        world.set_collisions_on(1, 2, false);
+        assert!(Collider::detect_overlaps(&world).is_empty());
+        assert!(Collider::detect_overlaps_brute_force(&world).is_empty());
+    }
+
+    #[test]
+    fn resolve_blocking_pushes_dynamic_actor_out() {
+        let mut world = World::new(64, 64);
+        let floor = world.add_new_actor(
+            None,
+            Some(Transform::new(V2::new(10.0, 12.0), V2::one())),
+            Some(Collider::new(
+                vec![ColliderPart {
+                    offset: V2::zero(),
+                    shape: ColliderShape::Box { extend: V2::new(20.0, 4.0) },
+                    collider_type: ColliderType::Blocking,
+                }],
+                None,
+            )),
+            None,
+            None,
+        );
+        let falling = world.add_new_actor(
+            None,
+            Some(Transform::new(V2::new(10.0, 9.5), V2::one())),
+            Some(Collider::new(
+                vec![ColliderPart {
+                    offset: V2::zero(),
+                    shape: ColliderShape::Box { extend: V2::new(2.0, 2.0) },
+                    collider_type: ColliderType::Blocking,
+                }],
+                None,
+            )),
+            Some(Physics::with_velocity(V2::new(3.0, 5.0))),
+            None,
+        );
+
+        let contacts = Collider::resolve_blocking(&mut world);
+
+        assert_eq!(contacts.len(), 1);
+        assert!((contacts[0].depth - 0.5).abs() < 1e-4);
+        assert!(contacts[0].normal_for(&falling).unwrap() == V2::new(0.0, -1.0));
+        assert!(contacts[0].normal_for(&floor).unwrap() == V2::new(0.0, 1.0));
+        assert!((world.get_transform(&falling).unwrap().center.y - 9.0).abs() < 1e-4);
+        assert!(world.get_transform(&floor).unwrap().center == V2::new(10.0, 12.0));
+        assert!(world.get_physics(&falling).unwrap().velocity == V2::new(3.0, 0.0));
+    }
+
+    #[test]
+    fn resolve_blocking_pushes_root_of_parented_blocker() {
+        let mut world = World::new(64, 64);
+        let floor = world.add_new_actor(
+            None,
+            Some(Transform::new(V2::new(10.0, 12.0), V2::one())),
+            Some(Collider::new(
+                vec![ColliderPart {
+                    offset: V2::zero(),
+                    shape: ColliderShape::Box { extend: V2::new(20.0, 4.0) },
+                    collider_type: ColliderType::Blocking,
+                }],
+                None,
+            )),
+            None,
+            None,
+        );
+        let body = world.add_new_actor(
+            None,
+            Some(Transform::new(V2::new(10.0, 7.5), V2::one())),
+            None,
+            Some(Physics::with_velocity(V2::new(3.0, 5.0))),
+            None,
+        );
+        let feet = world.add_new_actor(
+            None,
+            Some(Transform::new(V2::new(10.0, 9.5), V2::one())),
+            Some(Collider::new(
+                vec![ColliderPart {
+                    offset: V2::zero(),
+                    shape: ColliderShape::Box { extend: V2::new(2.0, 2.0) },
+                    collider_type: ColliderType::Blocking,
+                }],
+                None,
+            )),
+            None,
+            None,
+        );
+        world.set_parent(&feet, Some(&body));
+
+        let contacts = Collider::resolve_blocking(&mut world);
+        world.propagate_transforms();
+
+        assert_eq!(contacts.len(), 1);
+        assert!(contacts[0].normal_for(&feet).unwrap() == V2::new(0.0, -1.0));
+        assert!((world.get_transform(&body).unwrap().center.y - 7.0).abs() < 1e-4);
+        assert!((world.get_transform(&feet).unwrap().center.y - 9.0).abs() < 1e-4);
+        assert!(world.get_transform(&floor).unwrap().center == V2::new(10.0, 12.0));
+        assert!(world.get_physics(&body).unwrap().velocity == V2::new(3.0, 0.0));
+        assert!(Collider::resolve_blocking(&mut world).is_empty());
+    }
+
+    #[test]
+    fn broad_phase_matches_brute_force() {
+        for seed in 0..8 {
+            let world = create_random_world(seed, 300);
+            let overlaps = Collider::detect_overlaps(&world);
+            assert!(!overlaps.is_empty());
+            assert!(overlaps == Collider::detect_overlaps_brute_force(&world), "seed {}", seed);
+        }
     }
 }
@@ -0,0 +1,125 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
+
+use crate::engine::engine::ActorId;
+
+pub trait ComponentStore: Send {
+    fn remove_actor(&mut self, actor_id: &ActorId);
+    fn clear(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Send + 'static> ComponentStore for HashMap<ActorId, T> {
+    fn remove_actor(&mut self, actor_id: &ActorId) {
+        self.remove(actor_id);
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+pub type StoreCloner = fn(&dyn ComponentStore) -> Box<dyn ComponentStore>;
+
+fn clone_store<T: Clone + Send + 'static>(store: &dyn ComponentStore) -> Box<dyn ComponentStore> {
+    Box::new(store.as_any().downcast_ref::<HashMap<ActorId, T>>().unwrap().clone())
+}
+
+pub struct ComponentStorage {
+    stores: HashMap<TypeId, Box<dyn ComponentStore>>,
+    cloners: HashMap<TypeId, StoreCloner>,
+}
+
+impl ComponentStorage {
+    pub fn new() -> Self {
+        Self {
+            stores: HashMap::new(),
+            cloners: HashMap::new(),
+        }
+    }
+
+    pub fn register_cloneable<T: Clone + Send + 'static>(&mut self) {
+        self.cloners.insert(TypeId::of::<T>(), clone_store::<T>);
+    }
+
+    pub fn is_cloneable<T: 'static>(&self) -> bool {
+        self.cloners.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn get_store<T: 'static>(&self) -> Option<&HashMap<ActorId, T>> {
+        self.stores.get(&TypeId::of::<T>()).and_then(|f| f.as_any().downcast_ref::<HashMap<ActorId, T>>())
+    }
+
+    pub fn get_mut_store<T: 'static>(&mut self) -> Option<&mut HashMap<ActorId, T>> {
+        self.stores.get_mut(&TypeId::of::<T>()).and_then(|f| f.as_any_mut().downcast_mut::<HashMap<ActorId, T>>())
+    }
+
+    pub fn take_store<T: 'static>(&mut self) -> Option<Box<dyn ComponentStore>> {
+        self.stores.remove(&TypeId::of::<T>())
+    }
+
+    pub fn put_store<T: 'static>(&mut self, store: Box<dyn ComponentStore>) {
+        self.stores.insert(TypeId::of::<T>(), store);
+    }
+
+    pub fn insert<T: Send + 'static>(&mut self, actor_id: ActorId, component: T) -> Option<T> {
+        self.stores
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(HashMap::<ActorId, T>::new()))
+            .as_any_mut()
+            .downcast_mut::<HashMap<ActorId, T>>()
+            .unwrap()
+            .insert(actor_id, component)
+    }
+
+    pub fn get<T: 'static>(&self, actor_id: &ActorId) -> Option<&T> {
+        self.get_store::<T>().and_then(|f| f.get(actor_id))
+    }
+
+    pub fn get_mut<T: 'static>(&mut self, actor_id: &ActorId) -> Option<&mut T> {
+        self.get_mut_store::<T>().and_then(|f| f.get_mut(actor_id))
+    }
+
+    pub fn remove<T: 'static>(&mut self, actor_id: &ActorId) -> Option<T> {
+        self.get_mut_store::<T>().and_then(|f| f.remove(actor_id))
+    }
+
+    pub fn remove_actor(&mut self, actor_id: &ActorId) {
+        for store in self.stores.values_mut() {
+            store.remove_actor(actor_id);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for store in self.stores.values_mut() {
+            store.clear();
+        }
+    }
+}
+
+impl Default for ComponentStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Only stores of registered cloneable types are copied, the rest are left out of the clone.
+impl Clone for ComponentStorage {
+    fn clone(&self) -> Self {
+        Self {
+            stores: self.stores.iter().filter_map(|(k, v)| self.cloners.get(k).map(|f| (*k, f(v.as_ref())))).collect(),
+            cloners: self.cloners.clone(),
+        }
+    }
+}
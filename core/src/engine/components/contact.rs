@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use crate::engine::{
+    components::{collider::CollisionMaskId, world::World},
+    engine::ActorId,
+    v2::V2,
+};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum CollisionPhase {
+    Begin,
+    Persist,
+    End,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CollisionEvent {
+    pub phase: CollisionPhase,
+    pub first: ActorId,
+    pub second: ActorId,
+    pub first_mask_id: CollisionMaskId,
+    pub second_mask_id: CollisionMaskId,
+}
+
+impl CollisionEvent {
+    pub fn involves(&self, actor_id: &ActorId) -> bool {
+        self.first == *actor_id || self.second == *actor_id
+    }
+
+    pub fn other(&self, actor_id: &ActorId) -> Option<ActorId> {
+        if self.first == *actor_id {
+            Some(self.second)
+        } else if self.second == *actor_id {
+            Some(self.first)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_between(&self, first: &ActorId, second: &ActorId) -> bool {
+        (self.first == *first && self.second == *second) || (self.first == *second && self.second == *first)
+    }
+}
+
+#[derive(Clone)]
+pub struct Contact {
+    pub first: ActorId,
+    pub second: ActorId,
+    pub normal: V2,
+    pub depth: f32,
+}
+
+impl Contact {
+    pub fn normal_for(&self, actor_id: &ActorId) -> Option<V2> {
+        if self.first == *actor_id {
+            Some(&self.normal * -1.0)
+        } else if self.second == *actor_id {
+            Some(self.normal.clone())
+        } else {
+            None
+        }
+    }
+}
+
+pub struct ContactTracker {
+    contacts: HashMap<(ActorId, ActorId), (CollisionMaskId, CollisionMaskId)>,
+}
+
+impl ContactTracker {
+    pub fn new() -> Self {
+        Self { contacts: HashMap::new() }
+    }
+
+    pub fn update(&mut self, overlaps: &HashMap<ActorId, Vec<ActorId>>, world: &World) -> Vec<CollisionEvent> {
+        let mut events = Vec::new();
+        let mut current = HashMap::new();
+
+        for (actor, others) in overlaps {
+            for other in others {
+                let pair = ContactTracker::order_pair(actor, other);
+                if pair.0 != *actor {
+                    continue;
+                }
+
+                let mask_ids = (
+                    world.get_collider(&pair.0).map_or(0, |f| f.mask_id),
+                    world.get_collider(&pair.1).map_or(0, |f| f.mask_id),
+                );
+                let phase = if self.contacts.contains_key(&pair) { CollisionPhase::Persist } else { CollisionPhase::Begin };
+                events.push(ContactTracker::create_event(phase, pair, mask_ids));
+                current.insert(pair, mask_ids);
+            }
+        }
+
+        for (pair, mask_ids) in &self.contacts {
+            if !current.contains_key(pair) {
+                events.push(ContactTracker::create_event(CollisionPhase::End, *pair, *mask_ids));
+            }
+        }
+
+        events.sort_by_key(|f| (f.first.index, f.first.generation, f.second.index, f.second.generation));
+        self.contacts = current;
+        events
+    }
+
+    pub fn clear(&mut self) {
+        self.contacts.clear();
+    }
+
+    fn order_pair(first: &ActorId, second: &ActorId) -> (ActorId, ActorId) {
+        if (first.index, first.generation) <= (second.index, second.generation) {
+            (*first, *second)
+        } else {
+            (*second, *first)
+        }
+    }
+
+    fn create_event(phase: CollisionPhase, pair: (ActorId, ActorId), mask_ids: (CollisionMaskId, CollisionMaskId)) -> CollisionEvent {
+        CollisionEvent {
+            phase,
+            first: pair.0,
+            second: pair.1,
+            first_mask_id: mask_ids.0,
+            second_mask_id: mask_ids.1,
+        }
+    }
+}
+
+impl Default for ContactTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::engine::{
+        components::{
+            collider::{Collider, CollisionMaskId},
+            contact::{CollisionEvent, CollisionPhase, ContactTracker},
+            world::World,
+        },
+        engine::ActorId,
+    };
+
+    fn overlaps(pairs: &[(ActorId, ActorId)]) -> HashMap<ActorId, Vec<ActorId>> {
+        let mut overlaps: HashMap<ActorId, Vec<ActorId>> = HashMap::new();
+        for (first, second) in pairs {
+            overlaps.entry(*first).or_default().push(*second);
+            overlaps.entry(*second).or_default().push(*first);
+        }
+        overlaps
+    }
+
+    fn summarize(events: &[CollisionEvent]) -> Vec<(CollisionPhase, ActorId, ActorId, CollisionMaskId, CollisionMaskId)> {
+        events.iter().map(|f| (f.phase, f.first, f.second, f.first_mask_id, f.second_mask_id)).collect()
+    }
+
+    #[test]
+    fn tracks_begin_persist_and_end() {
+        let mut world = World::new(64, 64);
+        let [a, b, c] = [1, 2, 0].map(|mask_id| world.add_new_actor(None, None, Some(Collider::new(Vec::new(), Some(mask_id))), None, None));
+        let mut tracker = ContactTracker::new();
+
+        let events = tracker.update(&overlaps(&[(a, b)]), &world);
+        assert_eq!(summarize(&events), [(CollisionPhase::Begin, a, b, 1, 2)]);
+
+        let events = tracker.update(&overlaps(&[(b, a), (a, c)]), &world);
+        assert_eq!(summarize(&events), [(CollisionPhase::Persist, a, b, 1, 2), (CollisionPhase::Begin, a, c, 1, 0)]);
+
+        world.remove_actor(&b);
+        let events = tracker.update(&overlaps(&[(a, c)]), &world);
+        assert_eq!(summarize(&events), [(CollisionPhase::End, a, b, 1, 2), (CollisionPhase::Persist, a, c, 1, 0)]);
+
+        let events = tracker.update(&overlaps(&[]), &world);
+        assert_eq!(summarize(&events), [(CollisionPhase::End, a, c, 1, 0)]);
+
+        assert!(tracker.update(&overlaps(&[]), &world).is_empty());
+    }
+
+    #[test]
+    fn clear_forgets_contacts() {
+        let mut world = World::new(64, 64);
+        let [a, b] = [0, 0].map(|mask_id| world.add_new_actor(None, None, Some(Collider::new(Vec::new(), Some(mask_id))), None, None));
+        let mut tracker = ContactTracker::new();
+
+        tracker.update(&overlaps(&[(a, b)]), &world);
+        tracker.clear();
+
+        assert!(tracker.update(&overlaps(&[]), &world).is_empty());
+        assert_eq!(summarize(&tracker.update(&overlaps(&[(a, b)]), &world)), [(CollisionPhase::Begin, a, b, 0, 0)]);
+    }
+}
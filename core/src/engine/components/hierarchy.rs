@@ -0,0 +1,11 @@
+use crate::engine::{engine::ActorId, v2::V2};
+
+#[derive(Clone)]
+pub struct Parent {
+    pub id: ActorId,
+    pub local_offset: V2,
+    pub local_rotation: f32,
+}
+
+#[derive(Clone)]
+pub struct Children(pub Vec<ActorId>);
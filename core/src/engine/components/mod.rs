@@ -2,3 +2,13 @@ pub mod world;
 pub mod transform;
 pub mod collider;
 pub mod physics;
+pub mod component_storage;
+pub mod name;
+pub mod query;
+pub mod blink;
+pub mod hierarchy;
+pub mod tags;
+pub mod resource;
+pub mod world_data;
+pub mod contact;
+pub mod sat;
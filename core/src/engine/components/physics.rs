@@ -1,7 +1,18 @@
-pub struct Physics;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::v2::V2;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Physics {
+    pub velocity: V2,
+}
 
 impl Physics {
     pub fn new() -> Self {
-        Self {}
+        Self { velocity: V2::zero() }
+    }
+
+    pub fn with_velocity(velocity: V2) -> Self {
+        Self { velocity }
     }
 }
@@ -0,0 +1,33 @@
+use crate::engine::{components::component_storage::ComponentStorage, engine::ActorId};
+
+pub trait ComponentSet {
+    type Refs<'a>;
+
+    fn fetch<'a>(components: &'a ComponentStorage, actor_id: &ActorId) -> Option<Self::Refs<'a>>;
+}
+
+impl ComponentSet for () {
+    type Refs<'a> = ();
+
+    fn fetch<'a>(_: &'a ComponentStorage, _: &ActorId) -> Option<Self::Refs<'a>> {
+        Some(())
+    }
+}
+
+macro_rules! impl_component_set {
+    ($($component:ident),+) => {
+        impl<$($component: 'static),+> ComponentSet for ($($component,)+) {
+            type Refs<'a> = ($(&'a $component,)+);
+
+            fn fetch<'a>(components: &'a ComponentStorage, actor_id: &ActorId) -> Option<Self::Refs<'a>> {
+                Some(($(components.get::<$component>(actor_id)?,)+))
+            }
+        }
+    };
+}
+
+impl_component_set!(A);
+impl_component_set!(A, B);
+impl_component_set!(A, B, C);
+impl_component_set!(A, B, C, D);
+impl_component_set!(A, B, C, D, E);
@@ -0,0 +1,27 @@
+use std::any::Any;
+
+pub trait Resource: Send {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+pub type ResourceCloner = fn(&dyn Resource) -> Box<dyn Resource>;
+
+pub fn clone_resource<T: Clone + Send + 'static>(resource: &dyn Resource) -> Box<dyn Resource> {
+    Box::new(resource.as_any().downcast_ref::<T>().unwrap().clone())
+}
+
+impl<T: Send + 'static> Resource for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
@@ -0,0 +1,194 @@
+use crate::engine::v2::V2;
+
+const EPSILON: f32 = 1e-6;
+
+pub struct ConvexShape {
+    pub vertices: Vec<V2>,
+    pub radius: f32,
+}
+
+pub struct Penetration {
+    pub normal: V2,
+    pub depth: f32,
+}
+
+impl ConvexShape {
+    pub fn new(vertices: Vec<V2>, radius: f32) -> Self {
+        Self { vertices, radius }
+    }
+
+    pub fn project(&self, axis: &V2) -> (f32, f32) {
+        let (min, max) = self
+            .vertices
+            .iter()
+            .map(|f| f.dot(axis))
+            .fold((f32::MAX, f32::MIN), |(min, max), f| (f32::min(min, f), f32::max(max, f)));
+        (min - self.radius, max + self.radius)
+    }
+
+    fn edges(&self) -> Vec<(&V2, &V2)> {
+        match self.vertices.len() {
+            0 | 1 => Vec::new(),
+            2 => vec![(&self.vertices[0], &self.vertices[1])],
+            count => (0..count).map(|f| (&self.vertices[f], &self.vertices[(f + 1) % count])).collect(),
+        }
+    }
+
+    fn edge_normals(&self) -> Vec<V2> {
+        self.edges()
+            .into_iter()
+            .map(|(start, end)| end - start)
+            .filter(|f| f.mag() > EPSILON)
+            .map(|f| V2::new(-f.y, f.x).norm())
+            .collect()
+    }
+}
+
+fn closest_point_on_segment(point: &V2, start: &V2, end: &V2) -> V2 {
+    let segment = end - start;
+    let length_squared = segment.dot(&segment);
+    if length_squared <= EPSILON {
+        return start.clone();
+    }
+
+    let t = ((point - start).dot(&segment) / length_squared).clamp(0.0, 1.0);
+    start + &(&segment * t)
+}
+
+fn closest_point_on_shape(point: &V2, shape: &ConvexShape) -> V2 {
+    let edges = shape.edges();
+    if edges.is_empty() {
+        return shape.vertices[0].clone();
+    }
+
+    edges
+        .into_iter()
+        .map(|(start, end)| closest_point_on_segment(point, start, end))
+        .min_by(|f, g| f.distance(point).total_cmp(&g.distance(point)))
+        .unwrap()
+}
+
+fn closest_feature_axis(first: &ConvexShape, second: &ConvexShape) -> Option<V2> {
+    let from_first = first.vertices.iter().map(|f| (f.clone(), closest_point_on_shape(f, second)));
+    let from_second = second.vertices.iter().map(|f| (closest_point_on_shape(f, first), f.clone()));
+    let (start, end) = from_first.chain(from_second).min_by(|(a, b), (c, d)| a.distance(b).total_cmp(&c.distance(d)))?;
+
+    let direction = &end - &start;
+    if direction.mag() > EPSILON { Some(direction.norm()) } else { None }
+}
+
+pub fn test_shapes(first: &ConvexShape, second: &ConvexShape) -> Option<Penetration> {
+    if first.vertices.is_empty() || second.vertices.is_empty() {
+        return None;
+    }
+
+    let mut axes = first.edge_normals();
+    axes.extend(second.edge_normals());
+    axes.extend(closest_feature_axis(first, second));
+    if axes.is_empty() {
+        axes.push(V2::right());
+    }
+
+    let mut least: Option<Penetration> = None;
+    for axis in axes {
+        let (first_min, first_max) = first.project(&axis);
+        let (second_min, second_max) = second.project(&axis);
+        let forward = first_max - second_min;
+        let backward = second_max - first_min;
+        if forward <= 0.0 || backward <= 0.0 {
+            return None;
+        }
+
+        let penetration = if forward <= backward {
+            Penetration { normal: axis, depth: forward }
+        } else {
+            Penetration { normal: &axis * -1.0, depth: backward }
+        };
+        if least.as_ref().is_none_or(|f| penetration.depth < f.depth) {
+            least = Some(penetration);
+        }
+    }
+
+    least
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{
+        components::{
+            collider::{ColliderPart, ColliderShape, ColliderType},
+            sat::{self, ConvexShape},
+            transform::Transform,
+        },
+        v2::V2,
+    };
+
+    const TOLERANCE: f32 = 1e-4;
+
+    fn create_shape(shape: ColliderShape, center: V2, rotation: f32) -> ConvexShape {
+        let mut transform = Transform::new(center, V2::one());
+        transform.rotation = rotation;
+        ColliderPart {
+            offset: V2::zero(),
+            shape,
+            collider_type: ColliderType::Blocking,
+        }
+        .get_convex_shape(&transform)
+    }
+
+    fn square(center: V2, rotation: f32) -> ConvexShape {
+        create_shape(ColliderShape::Box { extend: V2::new(2.0, 2.0) }, center, rotation)
+    }
+
+    #[test]
+    fn rotated_boxes_overlap_along_their_corner() {
+        let penetration = sat::test_shapes(&square(V2::zero(), 0.0), &square(V2::new(2.3, 0.0), 45.0)).unwrap();
+
+        assert!((penetration.normal.x - 1.0).abs() < TOLERANCE && penetration.normal.y.abs() < TOLERANCE);
+        assert!((penetration.depth - (1.0 + 2.0_f32.sqrt() - 2.3)).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn rotated_boxes_are_separated() {
+        assert!(sat::test_shapes(&square(V2::zero(), 0.0), &square(V2::new(2.3, 0.0), 0.0)).is_none());
+        assert!(sat::test_shapes(&square(V2::zero(), 45.0), &square(V2::new(2.0, 2.0), 45.0)).is_none());
+    }
+
+    #[test]
+    fn crossing_boxes_overlap_without_contained_vertices() {
+        let horizontal = create_shape(ColliderShape::Box { extend: V2::new(10.0, 2.0) }, V2::zero(), 0.0);
+        let vertical = create_shape(ColliderShape::Box { extend: V2::new(2.0, 10.0) }, V2::zero(), 0.0);
+
+        assert!(sat::test_shapes(&horizontal, &vertical).is_some());
+    }
+
+    #[test]
+    fn circle_overlaps_box_corner_along_the_diagonal() {
+        let circle = |center: V2| create_shape(ColliderShape::Circle { radius: 1.0 }, center, 0.0);
+        let penetration = sat::test_shapes(&square(V2::zero(), 0.0), &circle(V2::new(1.5, 1.5))).unwrap();
+        let diagonal = 0.5_f32.sqrt();
+
+        assert!((penetration.normal.x - diagonal).abs() < TOLERANCE && (penetration.normal.y - diagonal).abs() < TOLERANCE);
+        assert!((penetration.depth - (1.0 - diagonal)).abs() < TOLERANCE);
+        assert!(sat::test_shapes(&square(V2::zero(), 0.0), &circle(V2::new(1.8, 1.8))).is_none());
+    }
+
+    #[test]
+    fn capsules_overlap_end_to_end() {
+        let capsule = |center: V2| create_shape(ColliderShape::Capsule { half_length: 2.0, radius: 1.0 }, center, 0.0);
+        let penetration = sat::test_shapes(&capsule(V2::zero()), &capsule(V2::new(5.5, 0.0))).unwrap();
+
+        assert!((penetration.normal.x - 1.0).abs() < TOLERANCE && penetration.normal.y.abs() < TOLERANCE);
+        assert!((penetration.depth - 0.5).abs() < TOLERANCE);
+        assert!(sat::test_shapes(&capsule(V2::zero()), &capsule(V2::new(6.5, 0.0))).is_none());
+    }
+
+    #[test]
+    fn coincident_circles_fall_back_to_right_axis() {
+        let circle = create_shape(ColliderShape::Circle { radius: 1.0 }, V2::new(3.0, 3.0), 0.0);
+        let penetration = sat::test_shapes(&circle, &circle).unwrap();
+
+        assert!(penetration.normal == V2::right());
+        assert!((penetration.depth - 2.0).abs() < TOLERANCE);
+    }
+}
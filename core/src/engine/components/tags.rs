@@ -0,0 +1,4 @@
+use std::collections::HashSet;
+
+#[derive(Clone)]
+pub struct Tags(pub HashSet<String>);
@@ -1,21 +1,69 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
 use crate::engine::v2::V2;
 
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "TransformData")]
 pub struct Transform {
     pub center: V2,
+    #[serde(skip)]
+    pub previous_center: V2,
     pub size: V2,
     pub anchor_offset: V2,
     pub original_size: V2,
     pub rotation: f32,
 }
 
+#[derive(Deserialize)]
+struct TransformData {
+    center: V2,
+    size: V2,
+    #[serde(default)]
+    anchor_offset: V2,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    original_size: Option<V2>,
+    #[serde(default)]
+    rotation: f32,
+}
+
+fn deserialize_some<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<V2>, D::Error> {
+    V2::deserialize(deserializer).map(Some)
+}
+
+impl From<TransformData> for Transform {
+    fn from(data: TransformData) -> Self {
+        Self {
+            previous_center: data.center.clone(),
+            center: data.center,
+            original_size: data.original_size.unwrap_or_else(|| data.size.clone()),
+            size: data.size,
+            anchor_offset: data.anchor_offset,
+            rotation: data.rotation,
+        }
+    }
+}
+
 impl Transform {
     pub fn new(center: V2, size: V2) -> Self {
         Self {
+            previous_center: center.clone(),
             center,
+            original_size: size.clone(),
             size,
             anchor_offset: V2::zero(),
-            original_size: V2::zero(),
             rotation: 0.0,
         }
     }
+
+    pub fn get_scale(&self) -> V2 {
+        V2::new(
+            if self.original_size.x != 0.0 { self.size.x / self.original_size.x } else { 1.0 },
+            if self.original_size.y != 0.0 { self.size.y / self.original_size.y } else { 1.0 },
+        )
+    }
+
+    pub fn teleport(&mut self, center: V2) {
+        self.previous_center = center.clone();
+        self.center = center;
+    }
 }
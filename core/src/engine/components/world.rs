@@ -1,88 +1,334 @@
-use std::collections::HashMap;
+use std::{
+    any::TypeId,
+    cell::{RefCell, RefMut},
+    collections::{HashMap, HashSet},
+};
 
 use crate::engine::{
+    asyncable::{AsyncableFunction, AsyncableId, AsyncableQueue, AsyncableType},
     color_matrix::ColorMatrix,
+    commands::Commands,
     components::{
         collider::{Collider, CollisionMask, CollisionMaskId},
+        component_storage::ComponentStorage,
+        hierarchy::{Children, Parent},
+        name::Name,
         physics::Physics,
+        blink::Blink,
+        query::ComponentSet,
+        resource::{Resource, ResourceCloner, clone_resource},
+        tags::Tags,
         transform::Transform,
     },
     engine::ActorId,
+    scene::{SceneId, SceneRequests},
+    v2::V2,
 };
 
+pub struct WorldSnapshot {
+    world: World,
+}
+
 pub struct World {
     pub all_actors: Vec<ActorId>,
-    names: HashMap<ActorId, Option<String>>,
-    transforms: HashMap<ActorId, Option<Transform>>,
-    colliders: HashMap<ActorId, Option<Collider>>,
-    physics: HashMap<ActorId, Option<Physics>>,
-    renders: HashMap<ActorId, Option<ColorMatrix>>,
+    generations: Vec<u16>,
+    free_indices: Vec<u32>,
+    components: ComponentStorage,
+    resources: HashMap<TypeId, Box<dyn Resource>>,
+    resource_cloners: HashMap<TypeId, ResourceCloner>,
+    commands: RefCell<Commands>,
+    scene_id: SceneId,
+    asyncable_queue: AsyncableQueue,
+    scene_requests: SceneRequests,
 
     collision_matrix: [CollisionMask; CollisionMaskId::MAX as usize],
+    interpolation_alpha: f32,
+    screen_width: u16,
+    screen_height: u16,
 }
 
 impl World {
-    pub fn new() -> Self {
+    pub fn new(screen_width: u16, screen_height: u16) -> Self {
+        let mut components = ComponentStorage::new();
+        components.register_cloneable::<Name>();
+        components.register_cloneable::<Tags>();
+        components.register_cloneable::<Transform>();
+        components.register_cloneable::<Collider>();
+        components.register_cloneable::<Physics>();
+        components.register_cloneable::<ColorMatrix>();
+        components.register_cloneable::<Blink>();
+        components.register_cloneable::<Parent>();
+        components.register_cloneable::<Children>();
+
         Self {
             all_actors: Vec::new(),
-            names: HashMap::new(),
-            transforms: HashMap::new(),
-            colliders: HashMap::new(),
-            physics: HashMap::new(),
-            renders: HashMap::new(),
+            generations: Vec::new(),
+            free_indices: Vec::new(),
+            components,
+            resources: HashMap::new(),
+            resource_cloners: HashMap::new(),
+            commands: RefCell::new(Commands::new()),
+            scene_id: 0,
+            asyncable_queue: AsyncableQueue::new(),
+            scene_requests: SceneRequests::new(),
 
             collision_matrix: [CollisionMask::MAX; CollisionMaskId::MAX as usize],
+            interpolation_alpha: 1.0,
+            screen_width,
+            screen_height,
         }
     }
 
+    pub fn attach_to_scene(&mut self, scene_id: SceneId, asyncable_queue: AsyncableQueue, scene_requests: SceneRequests) {
+        self.scene_id = scene_id;
+        self.asyncable_queue = asyncable_queue;
+        self.scene_requests = scene_requests;
+    }
+
+    pub fn get_scene_id(&self) -> SceneId {
+        self.scene_id
+    }
+
+    pub fn scene_requests(&self) -> &SceneRequests {
+        &self.scene_requests
+    }
+
+    pub fn add_asyncable(&self, function: AsyncableFunction, ms: f32, asyncable_type: AsyncableType) -> AsyncableId {
+        self.asyncable_queue.add(self.scene_id, function, ms, asyncable_type)
+    }
+
+    pub fn remove_asyncable(&self, id: AsyncableId) {
+        self.asyncable_queue.remove(id);
+    }
+
     pub fn get_collision_matrix(&self, index: CollisionMaskId) -> CollisionMask {
         self.collision_matrix[index as usize]
     }
 
+    pub fn are_masks_colliding(&self, first: CollisionMaskId, second: CollisionMaskId) -> bool {
+        CollisionMask::checked_shl(1, second as u32).is_some_and(|f| self.get_collision_matrix(first) & f != 0)
+    }
+
+    pub fn get_collision_matrixes(&self) -> &[CollisionMask] {
+        &self.collision_matrix
+    }
+
+    pub fn set_collision_matrixes(&mut self, collision_matrix: &[CollisionMask]) {
+        for (index, mask) in collision_matrix.iter().take(self.collision_matrix.len()).enumerate() {
+            self.collision_matrix[index] = *mask;
+        }
+    }
+
     pub fn set_collisions_on(&mut self, first: CollisionMaskId, second: CollisionMaskId, on: bool) {
-        let new_value = if on { 1 } else { 0 };
-        self.collision_matrix[first as usize] |= new_value << second;
-        self.collision_matrix[second as usize] |= new_value << first;
+        if on {
+            self.collision_matrix[first as usize] |= 1 << second;
+            self.collision_matrix[second as usize] |= 1 << first;
+        } else {
+            self.collision_matrix[first as usize] &= !(1 << second);
+            self.collision_matrix[second as usize] &= !(1 << first);
+        }
+    }
+
+    pub fn get_screen_width(&self) -> u16 {
+        self.screen_width
+    }
+
+    pub fn get_screen_height(&self) -> u16 {
+        self.screen_height
+    }
+
+    pub fn get_screen_size(&self) -> V2 {
+        V2::new(self.screen_width as f32, self.screen_height as f32)
+    }
+
+    pub fn insert_resource<T: Send + 'static>(&mut self, resource: T) -> Option<T> {
+        self.resources
+            .insert(TypeId::of::<T>(), Box::new(resource))
+            .and_then(|f| f.into_any().downcast::<T>().ok())
+            .map(|f| *f)
+    }
+
+    pub fn get_resource<T: 'static>(&self) -> Option<&T> {
+        self.resources.get(&TypeId::of::<T>()).and_then(|f| f.as_ref().as_any().downcast_ref::<T>())
+    }
+
+    pub fn get_mut_resource<T: 'static>(&mut self) -> Option<&mut T> {
+        self.resources.get_mut(&TypeId::of::<T>()).and_then(|f| f.as_mut().as_any_mut().downcast_mut::<T>())
+    }
+
+    pub fn remove_resource<T: 'static>(&mut self) -> Option<T> {
+        self.resources.remove(&TypeId::of::<T>()).and_then(|f| f.into_any().downcast::<T>().ok()).map(|f| *f)
+    }
+
+    pub fn has_resource<T: 'static>(&self) -> bool {
+        self.resources.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn register_snapshot_component<T: Clone + Send + 'static>(&mut self) {
+        self.components.register_cloneable::<T>();
+    }
+
+    pub fn register_snapshot_resource<T: Clone + Send + 'static>(&mut self) {
+        self.resource_cloners.insert(TypeId::of::<T>(), clone_resource::<T>);
+    }
+
+    /// Only components and resources registered for snapshots are captured, everything else is missing after a restore.
+    pub fn snapshot(&self) -> WorldSnapshot {
+        WorldSnapshot { world: self.clone() }
+    }
+
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        *self = snapshot.world.clone();
+    }
+
+    pub fn commands(&self) -> RefMut<'_, Commands> {
+        self.commands.borrow_mut()
+    }
+
+    pub fn apply_commands(&mut self) {
+        loop {
+            let commands = self.commands.get_mut().take();
+            if commands.is_empty() {
+                break;
+            }
+
+            for command in commands {
+                command(self);
+            }
+        }
+    }
+
+    pub fn get_interpolation_alpha(&self) -> f32 {
+        self.interpolation_alpha
+    }
+
+    pub fn set_interpolation_alpha(&mut self, interpolation_alpha: f32) {
+        self.interpolation_alpha = interpolation_alpha;
+    }
+
+    pub fn store_previous_transforms(&mut self) {
+        for transform in self.components.get_mut_store::<Transform>().into_iter().flat_map(|f| f.values_mut()) {
+            transform.previous_center = transform.center.clone();
+        }
+    }
+
+    pub fn insert<T: Send + 'static>(&mut self, actor_id: &ActorId, component: T) -> Option<T> {
+        if !self.is_alive(actor_id) {
+            return None;
+        }
+
+        self.components.insert(*actor_id, component)
+    }
+
+    pub fn get<T: 'static>(&self, actor_id: &ActorId) -> Option<&T> {
+        self.components.get::<T>(actor_id)
+    }
+
+    pub fn get_mut<T: 'static>(&mut self, actor_id: &ActorId) -> Option<&mut T> {
+        self.components.get_mut::<T>(actor_id)
+    }
+
+    pub fn remove<T: 'static>(&mut self, actor_id: &ActorId) -> Option<T> {
+        self.components.remove::<T>(actor_id)
+    }
+
+    pub fn has<T: 'static>(&self, actor_id: &ActorId) -> bool {
+        self.get::<T>(actor_id).is_some()
+    }
+
+    pub fn query<Q: ComponentSet>(&self) -> Vec<ActorId> {
+        self.all_actors.iter().filter(|f| Q::fetch(&self.components, f).is_some()).cloned().collect()
+    }
+
+    pub fn query_ref<Q: ComponentSet>(&self) -> Vec<(ActorId, Q::Refs<'_>)> {
+        self.all_actors.iter().filter_map(|f| Q::fetch(&self.components, f).map(|g| (*f, g))).collect()
+    }
+
+    pub fn query_mut<M: 'static, Q: ComponentSet>(&mut self, mut function: impl FnMut(&ActorId, &mut M, Q::Refs<'_>)) {
+        let Some(mut store) = self.components.take_store::<M>() else {
+            return;
+        };
+
+        let mutable_store = store.as_mut().as_any_mut().downcast_mut::<HashMap<ActorId, M>>().unwrap();
+        for actor_id in &self.all_actors {
+            if let Some(mutable) = mutable_store.get_mut(actor_id)
+                && let Some(refs) = Q::fetch(&self.components, actor_id)
+            {
+                function(actor_id, mutable, refs);
+            }
+        }
+
+        self.components.put_store::<M>(store);
     }
 
     pub fn get_name(&self, actor_id: &ActorId) -> Option<&String> {
-        self.names.get(actor_id).unwrap().as_ref()
+        self.get::<Name>(actor_id).map(|f| &f.0)
     }
 
     pub fn get_mut_name(&mut self, actor_id: &ActorId) -> Option<&mut String> {
-        self.names.get_mut(actor_id).unwrap().as_mut()
+        self.get_mut::<Name>(actor_id).map(|f| &mut f.0)
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<ActorId> {
+        self.all_actors.iter().find(|f| self.get_name(f).is_some_and(|g| g == name)).cloned()
+    }
+
+    pub fn find_all_by_name(&self, name: &str) -> Vec<ActorId> {
+        self.all_actors.iter().filter(|f| self.get_name(f).is_some_and(|g| g == name)).cloned().collect()
+    }
+
+    pub fn add_tag(&mut self, actor_id: &ActorId, tag: &str) {
+        if let Some(tags) = self.get_mut::<Tags>(actor_id) {
+            tags.0.insert(String::from(tag));
+        } else {
+            self.insert(actor_id, Tags(HashSet::from([String::from(tag)])));
+        }
+    }
+
+    pub fn remove_tag(&mut self, actor_id: &ActorId, tag: &str) {
+        if let Some(tags) = self.get_mut::<Tags>(actor_id) {
+            tags.0.remove(tag);
+        }
+    }
+
+    pub fn has_tag(&self, actor_id: &ActorId, tag: &str) -> bool {
+        self.get::<Tags>(actor_id).is_some_and(|f| f.0.contains(tag))
+    }
+
+    pub fn actors_with_tag(&self, tag: &str) -> Vec<ActorId> {
+        self.query_ref::<(Tags,)>().into_iter().filter(|(_, (tags,))| tags.0.contains(tag)).map(|(f, _)| f).collect()
     }
 
     pub fn get_transform(&self, actor_id: &ActorId) -> Option<&Transform> {
-        self.transforms.get(actor_id).unwrap().as_ref()
+        self.get::<Transform>(actor_id)
     }
 
     pub fn get_mut_transform(&mut self, actor_id: &ActorId) -> Option<&mut Transform> {
-        self.transforms.get_mut(actor_id).unwrap().as_mut()
+        self.get_mut::<Transform>(actor_id)
     }
 
     pub fn get_collider(&self, actor_id: &ActorId) -> Option<&Collider> {
-        self.colliders.get(actor_id).unwrap().as_ref()
+        self.get::<Collider>(actor_id)
     }
 
     pub fn get_mut_collider(&mut self, actor_id: &ActorId) -> Option<&mut Collider> {
-        self.colliders.get_mut(actor_id).unwrap().as_mut()
+        self.get_mut::<Collider>(actor_id)
     }
 
     pub fn get_physics(&self, actor_id: &ActorId) -> Option<&Physics> {
-        self.physics.get(actor_id).unwrap().as_ref()
+        self.get::<Physics>(actor_id)
     }
 
     pub fn get_mut_physics(&mut self, actor_id: &ActorId) -> Option<&mut Physics> {
-        self.physics.get_mut(actor_id).unwrap().as_mut()
+        self.get_mut::<Physics>(actor_id)
     }
 
     pub fn get_render(&self, actor_id: &ActorId) -> Option<&ColorMatrix> {
-        self.renders.get(actor_id).unwrap().as_ref()
+        self.get::<ColorMatrix>(actor_id)
     }
 
     pub fn get_mut_render(&mut self, actor_id: &ActorId) -> Option<&mut ColorMatrix> {
-        self.renders.get_mut(actor_id).unwrap().as_mut()
+        self.get_mut::<ColorMatrix>(actor_id)
     }
 
     pub fn add_new_actor(
@@ -93,37 +339,314 @@ impl World {
         physics: Option<Physics>,
         render: Option<ColorMatrix>,
     ) -> ActorId {
-        let mut new_actor_id = 0;
-        for i in 0..=u16::MAX {
-            new_actor_id = i;
-            if !self.all_actors.contains(&new_actor_id) {
-                break;
-            }
-        }
+        let index = self.free_indices.pop().unwrap_or_else(|| {
+            self.generations.push(0);
+            u32::try_from(self.generations.len() - 1).expect("World::add_new_actor: actor index space exhausted")
+        });
+        let new_actor_id = ActorId {
+            index,
+            generation: self.generations[index as usize],
+        };
 
         self.all_actors.push(new_actor_id);
-        self.all_actors.sort();
-        self.names.insert(new_actor_id, name);
-        self.transforms.insert(new_actor_id, transform);
-        self.colliders.insert(new_actor_id, collider);
-        self.physics.insert(new_actor_id, physics);
-        self.renders.insert(new_actor_id, render);
+        if let Some(name) = name {
+            self.components.insert(new_actor_id, Name(name));
+        }
+        if let Some(transform) = transform {
+            self.components.insert(new_actor_id, transform);
+        }
+        if let Some(collider) = collider {
+            self.components.insert(new_actor_id, collider);
+        }
+        if let Some(physics) = physics {
+            self.components.insert(new_actor_id, physics);
+        }
+        if let Some(render) = render {
+            self.components.insert(new_actor_id, render);
+        }
         new_actor_id
     }
 
+    pub fn is_alive(&self, actor_id: &ActorId) -> bool {
+        self.generations.get(actor_id.index as usize) == Some(&actor_id.generation)
+    }
+
     pub fn remove_actor(&mut self, actor_id: &ActorId) {
-        self.names.remove(actor_id);
-        self.transforms.remove(actor_id);
-        self.colliders.remove(actor_id);
-        self.physics.remove(actor_id);
-        self.renders.remove(actor_id);
+        if !self.is_alive(actor_id) {
+            return;
+        }
+
+        for child in self.get_children(actor_id) {
+            self.remove_actor(&child);
+        }
+        self.detach_from_parent(actor_id);
+
+        self.all_actors.retain(|f| f != actor_id);
+        self.generations[actor_id.index as usize] = actor_id.generation.wrapping_add(1);
+        self.free_indices.push(actor_id.index);
+
+        self.components.remove_actor(actor_id);
+    }
+
+    pub fn get_parent(&self, actor_id: &ActorId) -> Option<ActorId> {
+        self.get::<Parent>(actor_id).map(|f| f.id)
+    }
+
+    pub fn get_root(&self, actor_id: &ActorId) -> ActorId {
+        let mut root = *actor_id;
+        while let Some(parent) = self.get_parent(&root) {
+            root = parent;
+        }
+        root
+    }
+
+    pub fn get_children(&self, actor_id: &ActorId) -> Vec<ActorId> {
+        self.get::<Children>(actor_id).map(|f| f.0.clone()).unwrap_or_default()
+    }
+
+    pub fn set_parent(&mut self, child: &ActorId, parent: Option<&ActorId>) {
+        if !self.is_alive(child) {
+            return;
+        }
+
+        if let Some(parent) = parent
+            && (!self.is_alive(parent) || self.is_descendant_or_self(parent, child))
+        {
+            return;
+        }
+
+        self.detach_from_parent(child);
+
+        let Some(parent) = parent else {
+            return;
+        };
+
+        let (local_offset, local_rotation) = match (self.get_transform(parent), self.get_transform(child)) {
+            (Some(parent_transform), Some(child_transform)) => (
+                (&child_transform.center - &parent_transform.center).rotated(-parent_transform.rotation),
+                child_transform.rotation - parent_transform.rotation,
+            ),
+            _ => (V2::zero(), 0.0),
+        };
+
+        self.insert(
+            child,
+            Parent {
+                id: *parent,
+                local_offset,
+                local_rotation,
+            },
+        );
+        if let Some(children) = self.get_mut::<Children>(parent) {
+            children.0.push(*child);
+        } else {
+            self.insert(parent, Children(vec![*child]));
+        }
+    }
+
+    pub fn propagate_transforms(&mut self) {
+        let roots: Vec<ActorId> = self.query::<(Children,)>().into_iter().filter(|f| !self.has::<Parent>(f)).collect();
+        for root in roots {
+            self.propagate_transforms_from(&root);
+        }
+    }
+
+    fn propagate_transforms_from(&mut self, parent: &ActorId) {
+        let Some((parent_center, parent_rotation)) = self.get_transform(parent).map(|f| (f.center.clone(), f.rotation)) else {
+            return;
+        };
+
+        for child in self.get_children(parent) {
+            let Some((local_offset, local_rotation)) = self.get::<Parent>(&child).map(|f| (f.local_offset.clone(), f.local_rotation)) else {
+                continue;
+            };
+
+            if let Some(child_transform) = self.get_mut_transform(&child) {
+                child_transform.center = &parent_center + &local_offset.rotated(parent_rotation);
+                child_transform.rotation = parent_rotation + local_rotation;
+            }
+
+            self.propagate_transforms_from(&child);
+        }
+    }
+
+    fn detach_from_parent(&mut self, child: &ActorId) {
+        if let Some(parent) = self.remove::<Parent>(child)
+            && let Some(children) = self.get_mut::<Children>(&parent.id)
+        {
+            children.0.retain(|f| f != child);
+        }
+    }
+
+    fn is_descendant_or_self(&self, actor_id: &ActorId, ancestor: &ActorId) -> bool {
+        let mut current = Some(*actor_id);
+        while let Some(id) = current {
+            if id == *ancestor {
+                return true;
+            }
+            current = self.get_parent(&id);
+        }
+        false
     }
 
     pub fn clear_all(&mut self) {
-        self.names.clear();
-        self.transforms.clear();
-        self.colliders.clear();
-        self.physics.clear();
-        self.renders.clear();
+        for actor_id in &self.all_actors {
+            self.generations[actor_id.index as usize] = actor_id.generation.wrapping_add(1);
+            self.free_indices.push(actor_id.index);
+        }
+
+        self.all_actors.clear();
+        self.components.clear();
+        self.resources.clear();
+    }
+}
+
+impl Clone for World {
+    fn clone(&self) -> Self {
+        Self {
+            all_actors: self.all_actors.clone(),
+            generations: self.generations.clone(),
+            free_indices: self.free_indices.clone(),
+            components: self.components.clone(),
+            resources: self.resources.iter().filter_map(|(k, v)| self.resource_cloners.get(k).map(|f| (*k, f(v.as_ref())))).collect(),
+            resource_cloners: self.resource_cloners.clone(),
+            commands: RefCell::new(Commands::new()),
+            scene_id: self.scene_id,
+            asyncable_queue: self.asyncable_queue.clone(),
+            scene_requests: self.scene_requests.clone(),
+
+            collision_matrix: self.collision_matrix,
+            interpolation_alpha: self.interpolation_alpha,
+            screen_width: self.screen_width,
+            screen_height: self.screen_height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{
+        components::{
+            hierarchy::Children,
+            transform::Transform,
+            world::{World, WorldSnapshot},
+        },
+        engine::ActorId,
+        v2::V2,
+    };
+
+    fn add_actor(world: &mut World, center: V2) -> ActorId {
+        world.add_new_actor(None, Some(Transform::new(center, V2::one())), None, None, None)
+    }
+
+    fn assert_near(actual: &V2, expected: V2) {
+        assert!((actual.x - expected.x).abs() < 1e-4 && (actual.y - expected.y).abs() < 1e-4);
+    }
+
+    struct Handle;
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct Score(u32);
+
+    fn assert_send<T: Send>() {}
+
+    #[test]
+    fn worlds_can_move_between_threads() {
+        assert_send::<World>();
+        assert_send::<WorldSnapshot>();
+    }
+
+    #[test]
+    fn stale_handles_do_not_resolve_after_respawn() {
+        let mut world = World::new(64, 64);
+        let old = world.add_new_actor(Some(String::from("old")), Some(Transform::new(V2::zero(), V2::one())), None, None, None);
+        world.remove_actor(&old);
+        let new = world.add_new_actor(Some(String::from("new")), Some(Transform::new(V2::one(), V2::one())), None, None, None);
+
+        assert_eq!(new.index, old.index);
+        assert_ne!(new, old);
+        assert!(!world.is_alive(&old));
+        assert!(world.get_transform(&old).is_none());
+        assert!(world.get_name(&old).is_none());
+        assert_eq!(world.get_name(&new).map(|f| f.as_str()), Some("new"));
+        assert!(world.insert(&old, Transform::new(V2::zero(), V2::one())).is_none());
+        assert!(world.get_transform(&old).is_none());
+    }
+
+    #[test]
+    fn children_follow_parent_transform() {
+        let mut world = World::new(64, 64);
+        let parent = add_actor(&mut world, V2::new(10.0, 10.0));
+        let child = add_actor(&mut world, V2::new(12.0, 10.0));
+        let grandchild = add_actor(&mut world, V2::new(12.0, 11.0));
+        world.set_parent(&child, Some(&parent));
+        world.set_parent(&grandchild, Some(&child));
+
+        let transform = world.get_mut_transform(&parent).unwrap();
+        transform.center = V2::new(20.0, 10.0);
+        transform.rotation = 90.0;
+        world.propagate_transforms();
+
+        assert_near(&world.get_transform(&child).unwrap().center, V2::new(20.0, 12.0));
+        assert_near(&world.get_transform(&grandchild).unwrap().center, V2::new(19.0, 12.0));
+        assert!((world.get_transform(&grandchild).unwrap().rotation - 90.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn despawning_cleans_up_hierarchy() {
+        let mut world = World::new(64, 64);
+        let parent = add_actor(&mut world, V2::zero());
+        let first = add_actor(&mut world, V2::one());
+        let second = add_actor(&mut world, V2::one());
+        let grandchild = add_actor(&mut world, V2::one());
+        world.set_parent(&first, Some(&parent));
+        world.set_parent(&second, Some(&parent));
+        world.set_parent(&grandchild, Some(&second));
+
+        world.remove_actor(&first);
+        assert_eq!(world.get_children(&parent), [second]);
+
+        world.remove_actor(&parent);
+        assert!(!world.is_alive(&second) && !world.is_alive(&grandchild));
+        assert!(world.all_actors.is_empty());
+    }
+
+    #[test]
+    fn set_parent_ignores_dead_actors() {
+        let mut world = World::new(64, 64);
+        let parent = add_actor(&mut world, V2::zero());
+        let dead = add_actor(&mut world, V2::one());
+        world.remove_actor(&dead);
+
+        world.set_parent(&dead, Some(&parent));
+        assert!(world.get::<Children>(&parent).is_none());
+
+        let child = add_actor(&mut world, V2::one());
+        world.set_parent(&child, Some(&dead));
+        assert_eq!(world.get_parent(&child), None);
+    }
+
+    #[test]
+    fn snapshots_only_capture_registered_types() {
+        let mut world = World::new(64, 64);
+        let actor = add_actor(&mut world, V2::zero());
+        world.insert(&actor, Handle);
+        world.insert(&actor, Score(1));
+        world.insert_resource(Handle);
+        world.insert_resource(Score(2));
+        world.register_snapshot_component::<Score>();
+        world.register_snapshot_resource::<Score>();
+
+        let snapshot = world.snapshot();
+        world.get_mut::<Score>(&actor).unwrap().0 = 10;
+        world.get_mut_resource::<Score>().unwrap().0 = 20;
+        world.get_mut_transform(&actor).unwrap().center = V2::one();
+        world.restore(&snapshot);
+
+        assert_eq!(world.get::<Score>(&actor), Some(&Score(1)));
+        assert_eq!(world.get_resource::<Score>(), Some(&Score(2)));
+        assert_near(&world.get_transform(&actor).unwrap().center, V2::zero());
+        assert!(!world.has::<Handle>(&actor));
+        assert!(!world.has_resource::<Handle>());
     }
 }
@@ -0,0 +1,195 @@
+use ron::{error::SpannedError, ser::PrettyConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{
+    color_matrix::ColorMatrix,
+    components::{
+        collider::{Collider, CollisionMask},
+        physics::Physics,
+        transform::Transform,
+        world::World,
+    },
+    engine::ActorId,
+};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ActorData {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub transform: Option<Transform>,
+    #[serde(default)]
+    pub collider: Option<Collider>,
+    #[serde(default)]
+    pub physics: Option<Physics>,
+    #[serde(default)]
+    pub render: Option<ColorMatrix>,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum WorldDataError {
+    InvalidRender {
+        actor_index: usize,
+        width: usize,
+        height: usize,
+        data_len: usize,
+    },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WorldData {
+    #[serde(default)]
+    pub collision_matrix: Option<Vec<CollisionMask>>,
+    pub actors: Vec<ActorData>,
+}
+
+impl WorldData {
+    pub fn from_world(world: &World) -> Self {
+        let actors = world
+            .all_actors
+            .iter()
+            .map(|actor_id| ActorData {
+                name: world.get_name(actor_id).cloned(),
+                transform: world.get_transform(actor_id).cloned(),
+                collider: world.get_collider(actor_id).cloned(),
+                physics: world.get_physics(actor_id).cloned(),
+                render: world.get_render(actor_id).cloned(),
+            })
+            .collect();
+
+        Self {
+            collision_matrix: Some(world.get_collision_matrixes().to_vec()),
+            actors,
+        }
+    }
+
+    pub fn from_ron(source: &str) -> Result<Self, SpannedError> {
+        ron::from_str(source)
+    }
+
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, PrettyConfig::new().compact_arrays(true))
+    }
+
+    pub fn validate(&self) -> Result<(), WorldDataError> {
+        for (actor_index, actor) in self.actors.iter().enumerate() {
+            if let Some(render) = &actor.render
+                && render.width.checked_mul(render.height) != Some(render.data.len())
+            {
+                return Err(WorldDataError::InvalidRender {
+                    actor_index,
+                    width: render.width,
+                    height: render.height,
+                    data_len: render.data.len(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn load_into(&self, world: &mut World) -> Result<Vec<ActorId>, WorldDataError> {
+        self.validate()?;
+
+        if let Some(collision_matrix) = &self.collision_matrix {
+            world.set_collision_matrixes(collision_matrix);
+        }
+
+        Ok(self
+            .actors
+            .iter()
+            .map(|actor| {
+                let transform = actor.transform.clone().map(|mut transform| {
+                    transform.previous_center = transform.center.clone();
+                    transform
+                });
+                world.add_new_actor(actor.name.clone(), transform, actor.collider.clone(), actor.physics.clone(), actor.render.clone())
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{
+        actor::rectangle_actor::create_rectangle_actor,
+        color::Color,
+        components::{
+            collider::ColliderType,
+            world::World,
+            world_data::{WorldData, WorldDataError},
+        },
+        v2::V2,
+    };
+
+    #[test]
+    fn round_trips_through_ron() {
+        let mut world = World::new(64, 64);
+        create_rectangle_actor(
+            &mut world,
+            V2::new(5.0, 6.0),
+            V2::new(4.0, 2.0),
+            Color::red(),
+            Some(ColliderType::Blocking),
+            Some(String::from("wall")),
+        );
+        world.set_collisions_on(1, 2, true);
+
+        let source = WorldData::from_world(&world).to_ron().unwrap();
+        let mut loaded = World::new(64, 64);
+        let actors = WorldData::from_ron(&source).unwrap().load_into(&mut loaded).unwrap();
+
+        assert_eq!(actors.len(), 1);
+        assert_eq!(loaded.get_name(&actors[0]).map(|f| f.as_str()), Some("wall"));
+        let transform = loaded.get_transform(&actors[0]).unwrap();
+        assert!(transform.center == V2::new(5.0, 6.0) && transform.previous_center == transform.center);
+        assert!(loaded.get_collider(&actors[0]).unwrap().collider_parts[0].collider_type == ColliderType::Blocking);
+        let render = loaded.get_render(&actors[0]).unwrap();
+        assert!((render.width, render.height) == (4, 2) && render.data.iter().all(|f| *f == Color::red()));
+        assert_eq!(loaded.get_collision_matrixes(), world.get_collision_matrixes());
+    }
+
+    #[test]
+    fn rejects_render_with_mismatched_data() {
+        let data = WorldData::from_ron("(actors: [(render: Some((width: 4, height: 4, data: [])))])").unwrap();
+        let mut world = World::new(64, 64);
+
+        assert_eq!(
+            data.load_into(&mut world),
+            Err(WorldDataError::InvalidRender {
+                actor_index: 0,
+                width: 4,
+                height: 4,
+                data_len: 0
+            })
+        );
+        assert!(world.all_actors.is_empty());
+    }
+
+    #[test]
+    fn rejects_render_with_overflowing_size() {
+        let data = WorldData::from_ron("(actors: [(render: Some((width: 4294967296, height: 4294967296, data: [])))])").unwrap();
+
+        assert_eq!(
+            data.validate(),
+            Err(WorldDataError::InvalidRender {
+                actor_index: 0,
+                width: 4294967296,
+                height: 4294967296,
+                data_len: 0
+            })
+        );
+    }
+
+    #[test]
+    fn loads_transform_with_only_center_and_size() {
+        let data = WorldData::from_ron("(actors: [(transform: Some((center: (x: 1.0, y: 2.0), size: (x: 4.0, y: 2.0))))])").unwrap();
+        let mut world = World::new(64, 64);
+        let actors = data.load_into(&mut world).unwrap();
+
+        let transform = world.get_transform(&actors[0]).unwrap();
+        assert!(transform.center == V2::new(1.0, 2.0) && transform.previous_center == transform.center);
+        assert!(transform.original_size == transform.size && transform.get_scale() == V2::one());
+        assert!(transform.anchor_offset == V2::zero() && transform.rotation == 0.0);
+    }
+}
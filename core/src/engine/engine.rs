@@ -1,100 +1,305 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use crate::{
-    engine::{
-        asyncable::AsyncableStorage,
-        color::Color,
-        color_matrix::ColorMatrix,
-        components::{collider::Collider, world::World},
-        input::input::Input,
-        scene::{EmptyScene, Scene},
-        threading_provider::Thread,
-    },
-    scenes::pong::pong_scene::PongScene,
+use crate::engine::{
+    asyncable::AsyncableStorage,
+    clock::Clock,
+    color::Color,
+    color_matrix::ColorMatrix,
+    engine_config::EngineConfig,
+    components::{collider::Collider, contact::ContactTracker, transform::Transform, world::{World, WorldSnapshot}},
+    input::input::{EmptyInput, Input},
+    scene::{PushMode, Scene, SceneEntry, SceneError, SceneFactory, SceneId, SceneRequest, SceneRequests},
+    system::{Scheduler, Stage},
+    threading_provider::Thread,
+    time_control::TimeControl,
+    transition::{Transition, TransitionInProgress},
 };
 use std::{
-    sync::Arc,
-    time::{Duration, Instant},
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
 };
 
-pub static SCREEN_SIZE: u8 = 64;
-pub type TempActorId = u16;
-pub type ActorId = u16;
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct ActorId {
+    pub index: u32,
+    pub generation: u16,
+}
+pub const MAX_FIXED_STEPS_PER_FRAME: u8 = 5;
 
 pub struct Engine {
     pub delta_time: f32,
+    config: EngineConfig,
+    fixed_delta_time: f32,
+    accumulator: f32,
     is_blue: bool,
-    world: World,
-    current_scene: Box<dyn Scene>,
+    scene_stack: Vec<SceneEntry>,
+    transition: Option<TransitionInProgress>,
+    next_scene_id: SceneId,
     is_any_scene: bool,
+    scenes: HashMap<String, SceneFactory>,
+    start_scene: String,
     pub input: Box<dyn Input>,
+    empty_input: Box<dyn Input>,
     asyncable_storage: AsyncableStorage,
+    scene_requests: SceneRequests,
+    scene_errors: Vec<SceneError>,
+    quit_signal: Arc<AtomicBool>,
+    clock: Box<dyn Clock>,
+    last_frame_time: Duration,
+    time_control: Arc<Mutex<TimeControl>>,
+    saved_state: Option<(SceneId, WorldSnapshot)>,
 }
 
 impl Engine {
-    pub fn new(input: Box<dyn Input>) -> Self {
+    pub fn new(input: Box<dyn Input>, clock: Box<dyn Clock>, config: EngineConfig, start_scene: &str) -> Self {
         Self {
             delta_time: 0.0,
+            fixed_delta_time: 1.0 / config.fixed_update_rate,
+            config,
+            accumulator: 0.0,
             is_blue: false,
-            world: World::new(),
-            current_scene: Box::new(EmptyScene::new()),
+            scene_stack: Vec::new(),
+            transition: None,
+            next_scene_id: 0,
             is_any_scene: false,
+            scenes: HashMap::new(),
+            start_scene: String::from(start_scene),
             input: input,
+            empty_input: Box::new(EmptyInput::new()),
             asyncable_storage: AsyncableStorage::new(),
+            scene_requests: SceneRequests::new(),
+            scene_errors: Vec::new(),
+            quit_signal: Arc::new(AtomicBool::new(false)),
+            last_frame_time: clock.now(),
+            clock,
+            time_control: Arc::new(Mutex::new(TimeControl::new())),
+            saved_state: None,
         }
     }
 
-    pub fn run<T: Thread>(&mut self, on_frame_finished: Arc<dyn Fn(ColorMatrix) + Send + Sync + 'static>) {
-        let mut last = Instant::now();
-        let target_frame = Duration::from_millis(33);
+    pub fn run<T: Thread>(&mut self, on_frame_finished: Arc<dyn Fn(ColorMatrix) + Send + Sync + 'static>) -> Result<(), SceneError> {
+        if !self.is_any_scene && !self.has_scene(&self.start_scene) {
+            return Err(SceneError::UnknownScene(self.start_scene.clone()));
+        }
 
-        loop {
-            let frame_start = Instant::now();
-            let dt = frame_start.duration_since(last);
-            last = frame_start;
-            let delta_time = dt.as_secs_f32();
+        self.last_frame_time = self.clock.now();
+        let target_frame = Duration::from_millis(self.config.target_frame_ms);
 
-            self.delta_time = delta_time;
-            self.is_blue = !self.is_blue;
+        while !self.is_quit_requested() {
+            let frame_start = self.clock.now();
+            on_frame_finished(self.step_with_clock());
 
-            if !self.is_any_scene {
-                self.open_scene(|| Box::new(PongScene::new()));
-                self.is_any_scene = true;
+            let frame_time = self.clock.now().saturating_sub(frame_start);
+            if frame_time < target_frame {
+                T::sleep_for((target_frame - frame_time).as_millis() as u64);
             }
+        }
 
-            {
-                let mut_scene = self.current_scene.as_mut();
-                self.input.as_mut().update(delta_time);
-                mut_scene.tick(&self.input, &mut self.world, delta_time);
-                self.asyncable_storage.update(&mut self.world, delta_time);
-            }
+        self.shutdown();
+        Ok(())
+    }
 
-            {
-                let overlaps = Collider::detect_overlaps(&self.world);
-                let mut_scene = self.current_scene.as_mut();
-                mut_scene.on_overlaps(&overlaps, &mut self.world, delta_time);
+    pub fn shutdown(&mut self) {
+        self.clear_scene_stack();
+    }
 
-                on_frame_finished(self.combine_color_matrixes());
+    pub fn step_with_clock(&mut self) -> ColorMatrix {
+        let now = self.clock.now();
+        let delta_time = now.saturating_sub(self.last_frame_time);
+        self.last_frame_time = now;
+        self.step(delta_time.as_secs_f32())
+    }
 
-                self.input.as_mut().late_update(delta_time);
+    pub fn step(&mut self, unscaled_delta_time: f32) -> ColorMatrix {
+        let delta_time = self.time_control.lock().unwrap().scale_delta_time(unscaled_delta_time, self.fixed_delta_time);
+        self.delta_time = delta_time;
+        self.is_blue = !self.is_blue;
+
+        if !self.is_any_scene {
+            if let Err(error) = self.open_scene_by_name(&self.start_scene.clone()) {
+                self.scene_errors.push(error);
             }
+            self.is_any_scene = true;
+        }
 
-            let frame_time = frame_start.elapsed();
-            if frame_time < target_frame {
-                T::sleep_for((target_frame - frame_time).as_millis() as u64);
+        self.input.as_mut().update(delta_time);
+
+        let ticking_from = self.get_ticking_from();
+        let top = self.scene_stack.len().saturating_sub(1);
+
+        for entry in self.scene_stack.iter_mut().skip(ticking_from) {
+            entry.scheduler.run_stage(Stage::PreUpdate, &mut entry.world, delta_time);
+        }
+
+        for (index, entry) in self.scene_stack.iter_mut().enumerate().skip(ticking_from) {
+            let input = if index == top { &self.input } else { &self.empty_input };
+            entry.scene.as_mut().tick(input, &mut entry.world, delta_time);
+            entry.scheduler.run_stage(Stage::Update, &mut entry.world, delta_time);
+            self.asyncable_storage.update(entry.id, &mut entry.world, delta_time);
+            entry.world.apply_commands();
+        }
+
+        self.accumulator += delta_time;
+        let mut fixed_steps = 0;
+        while self.accumulator >= self.fixed_delta_time && fixed_steps < MAX_FIXED_STEPS_PER_FRAME {
+            self.fixed_step(ticking_from, top);
+            self.accumulator -= self.fixed_delta_time;
+            fixed_steps += 1;
+        }
+        self.accumulator %= self.fixed_delta_time;
+        let interpolation_alpha = self.accumulator / self.fixed_delta_time;
+
+        for entry in self.scene_stack.iter_mut().skip(ticking_from) {
+            entry.world.set_interpolation_alpha(interpolation_alpha);
+        }
+
+        self.handle_scene_requests();
+
+        let ticking_from = self.get_ticking_from();
+        for entry in self.scene_stack.iter_mut().skip(ticking_from) {
+            entry.scheduler.run_stage(Stage::Render, &mut entry.world, delta_time);
+            entry.world.propagate_transforms();
+        }
+
+        let screen = self.combine_color_matrixes(delta_time);
+
+        for entry in self.scene_stack.iter_mut().skip(ticking_from) {
+            entry.scene.as_mut().late_tick(&mut entry.world, delta_time);
+            entry.world.apply_commands();
+        }
+
+        self.input.as_mut().late_update(delta_time);
+
+        screen
+    }
+
+    fn fixed_step(&mut self, ticking_from: usize, top: usize) {
+        let fixed_delta_time = self.fixed_delta_time;
+
+        for (index, entry) in self.scene_stack.iter_mut().enumerate().skip(ticking_from) {
+            let input = if index == top { &self.input } else { &self.empty_input };
+            entry.world.store_previous_transforms();
+            entry.scene.as_mut().fixed_tick(input, &mut entry.world, fixed_delta_time);
+            entry.scheduler.run_stage(Stage::Physics, &mut entry.world, fixed_delta_time);
+            entry.world.propagate_transforms();
+            let contacts = Collider::resolve_blocking(&mut entry.world);
+            entry.world.propagate_transforms();
+
+            let overlaps = Collider::detect_overlaps(&entry.world);
+            let events = entry.contact_tracker.update(&overlaps, &entry.world);
+            entry.scene.as_mut().on_overlaps(&overlaps, &mut entry.world, fixed_delta_time);
+            entry.scene.as_mut().on_contacts(&contacts, &mut entry.world, fixed_delta_time);
+            entry.scene.as_mut().on_collisions(&events, &mut entry.world, fixed_delta_time);
+            entry.scheduler.run_stage(Stage::PostPhysics, &mut entry.world, fixed_delta_time);
+            entry.world.apply_commands();
+        }
+    }
+
+    pub fn get_config(&self) -> &EngineConfig {
+        &self.config
+    }
+
+    pub fn get_fixed_delta_time(&self) -> f32 {
+        self.fixed_delta_time
+    }
+
+    pub fn set_fixed_update_rate(&mut self, updates_per_second: f32) {
+        self.fixed_delta_time = 1.0 / updates_per_second;
+    }
+
+    pub fn quit(&self) {
+        self.quit_signal.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_quit_requested(&self) -> bool {
+        self.quit_signal.load(Ordering::Relaxed)
+    }
+
+    pub fn get_quit_signal(&self) -> Arc<AtomicBool> {
+        self.quit_signal.clone()
+    }
+
+    pub fn set_quit_signal(&mut self, quit_signal: Arc<AtomicBool>) {
+        self.quit_signal = quit_signal;
+    }
+
+    pub fn get_scene_requests(&self) -> SceneRequests {
+        self.scene_requests.clone()
+    }
+
+    pub fn set_scene_requests(&mut self, scene_requests: SceneRequests) {
+        self.scene_requests = scene_requests;
+        for entry in self.scene_stack.iter_mut() {
+            entry.world.attach_to_scene(entry.id, self.asyncable_storage.get_queue(), self.scene_requests.clone());
+        }
+    }
+
+    pub fn get_time_control(&self) -> Arc<Mutex<TimeControl>> {
+        self.time_control.clone()
+    }
+
+    pub fn set_time_control(&mut self, time_control: Arc<Mutex<TimeControl>>) {
+        self.time_control = time_control;
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_control.lock().unwrap().time_scale = time_scale;
+    }
+
+    pub fn set_paused(&mut self, is_paused: bool) {
+        self.time_control.lock().unwrap().is_paused = is_paused;
+    }
+
+    pub fn advance_frame(&mut self) {
+        self.time_control.lock().unwrap().advance_frame();
+    }
+
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.last_frame_time = clock.now();
+        self.clock = clock;
+    }
+
+    fn get_ticking_from(&self) -> usize {
+        let mut ticking_from = self.scene_stack.len();
+        for entry in self.scene_stack.iter().rev() {
+            ticking_from -= 1;
+            if entry.push_mode == PushMode::PauseBelow {
+                break;
             }
         }
+        ticking_from
     }
 
-    fn combine_color_matrixes(&mut self) -> super::matrix::Matrix<Color> {
-        let mut screen = ColorMatrix::new(SCREEN_SIZE, SCREEN_SIZE, Color::none());
-        for actor_id in &self.world.all_actors {
-            if let Some(render) = self.world.get_render(actor_id)
-                && let Some(transform) = self.world.get_transform(actor_id)
-            {
+    fn combine_color_matrixes(&mut self, delta_time: f32) -> ColorMatrix {
+        let screen = Engine::render_scene_stack(&self.scene_stack, &self.config);
+
+        let Some(transition) = self.transition.as_mut() else {
+            return screen;
+        };
+
+        transition.timer += delta_time;
+        let progress = if transition.transition.duration > 0.0 { transition.timer / transition.transition.duration } else { 1.0 };
+        let mixed = transition.transition.mix(&Engine::render_scene_stack(&transition.outgoing, &self.config), &screen, progress);
+
+        if progress >= 1.0 {
+            self.finish_transition();
+        }
+
+        mixed
+    }
+
+    fn render_scene_stack(scene_stack: &[SceneEntry], config: &EngineConfig) -> ColorMatrix {
+        let mut screen = ColorMatrix::new(config.width as usize, config.height as usize, Color::none());
+        for entry in scene_stack {
+            let interpolation_alpha = entry.world.get_interpolation_alpha();
+            for (_, (render, transform)) in entry.world.query_ref::<(ColorMatrix, Transform)>() {
                 screen.write(
                     render,
-                    &transform.center,
+                    &transform.previous_center.lerp(&transform.center, interpolation_alpha),
                     Some(transform.rotation.clone()),
                     Some(transform.anchor_offset.clone()),
                     Some(true),
@@ -108,9 +313,485 @@ impl Engine {
     where
         F: Fn() -> Box<dyn Scene>,
     {
-        self.world.clear_all();
-        let obj = new_scene_func();
-        self.current_scene = obj;
-        self.current_scene.as_mut().init(&mut self.world);
+        self.change_scene_stack(|engine| {
+            engine.clear_scene_stack();
+            engine.push_entry(new_scene_func(), PushMode::PauseBelow);
+        });
+    }
+
+    pub fn register_scene(&mut self, name: &str, factory: SceneFactory) {
+        self.scenes.insert(String::from(name), factory);
+    }
+
+    pub fn has_scene(&self, name: &str) -> bool {
+        self.scenes.contains_key(name)
+    }
+
+    pub fn open_scene_by_name(&mut self, name: &str) -> Result<(), SceneError> {
+        let scene = self.create_scene(name)?;
+        self.change_scene_stack(|engine| {
+            engine.clear_scene_stack();
+            engine.push_entry(scene, PushMode::PauseBelow);
+        });
+        Ok(())
+    }
+
+    pub fn open_scene_by_name_with_transition(&mut self, name: &str, transition: Transition) -> Result<(), SceneError> {
+        let scene = self.create_scene(name)?;
+        self.change_scene_stack(|engine| {
+            engine.finish_transition();
+            engine.transition = Some(TransitionInProgress {
+                transition,
+                outgoing: std::mem::take(&mut engine.scene_stack),
+                timer: 0.0,
+            });
+            engine.push_entry(scene, PushMode::PauseBelow);
+        });
+        Ok(())
+    }
+
+    pub fn push_scene_by_name(&mut self, name: &str, push_mode: PushMode) -> Result<(), SceneError> {
+        let scene = self.create_scene(name)?;
+        self.push_scene(scene, push_mode);
+        Ok(())
+    }
+
+    pub fn push_scene(&mut self, scene: Box<dyn Scene>, push_mode: PushMode) {
+        self.change_scene_stack(|engine| engine.push_entry(scene, push_mode));
+    }
+
+    pub fn pop_scene(&mut self) {
+        self.change_scene_stack(|engine| engine.pop_entry());
+    }
+
+    pub fn save_state(&mut self) {
+        if let Some(entry) = self.scene_stack.last() {
+            self.saved_state = Some((entry.id, entry.world.snapshot()));
+        }
+    }
+
+    pub fn load_state(&mut self) {
+        if let Some((scene_id, snapshot)) = &self.saved_state
+            && let Some(entry) = self.scene_stack.last_mut()
+            && entry.id == *scene_id
+        {
+            entry.world.restore(snapshot);
+            entry.contact_tracker.clear();
+        }
+    }
+
+    fn push_entry(&mut self, scene: Box<dyn Scene>, push_mode: PushMode) {
+        let mut entry = SceneEntry {
+            id: self.next_scene_id,
+            scene,
+            world: World::new(self.config.width, self.config.height),
+            scheduler: Scheduler::new(),
+            contact_tracker: ContactTracker::new(),
+            push_mode,
+        };
+        self.next_scene_id = self.next_scene_id.wrapping_add(1);
+
+        entry.world.attach_to_scene(entry.id, self.asyncable_storage.get_queue(), self.scene_requests.clone());
+        entry.scene.as_mut().init(&mut entry.world);
+        entry.scene.as_mut().register_systems(&mut entry.scheduler);
+        entry.world.apply_commands();
+        self.scene_stack.push(entry);
+    }
+
+    fn pop_entry(&mut self) {
+        if let Some(entry) = self.scene_stack.pop() {
+            self.exit_entry(entry);
+        }
+    }
+
+    fn exit_entry(&mut self, mut entry: SceneEntry) {
+        entry.scene.as_mut().on_exit(&mut entry.world);
+        self.asyncable_storage.remove_scene_asyncables(entry.id);
+    }
+
+    fn change_scene_stack<F>(&mut self, change: F)
+    where
+        F: FnOnce(&mut Engine),
+    {
+        let previous_ids: Vec<SceneId> = self.scene_stack.iter().map(|f| f.id).collect();
+        let previous_ticking: Vec<SceneId> = self.scene_stack.iter().skip(self.get_ticking_from()).map(|f| f.id).collect();
+        let previous_top = previous_ids.last().cloned();
+
+        change(self);
+
+        let ticking: Vec<SceneId> = self.scene_stack.iter().skip(self.get_ticking_from()).map(|f| f.id).collect();
+        let top = self.scene_stack.last().map(|f| f.id);
+
+        for entry in self.scene_stack.iter_mut() {
+            let was_present = previous_ids.contains(&entry.id);
+            let was_ticking = previous_ticking.contains(&entry.id);
+            let is_ticking = ticking.contains(&entry.id);
+
+            if was_present && previous_top == Some(entry.id) && top != Some(entry.id) {
+                entry.scene.as_mut().on_blur(&mut entry.world);
+            }
+            if was_ticking && !is_ticking {
+                entry.scene.as_mut().on_pause(&mut entry.world);
+            }
+            if was_present && !was_ticking && is_ticking {
+                entry.scene.as_mut().on_resume(&mut entry.world);
+            }
+            if top == Some(entry.id) && previous_top != Some(entry.id) {
+                entry.scene.as_mut().on_focus(&mut entry.world);
+            }
+        }
+    }
+
+    fn finish_transition(&mut self) {
+        if let Some(transition) = self.transition.take() {
+            for entry in transition.outgoing.into_iter().rev() {
+                self.exit_entry(entry);
+            }
+        }
+    }
+
+    fn clear_scene_stack(&mut self) {
+        self.finish_transition();
+        while !self.scene_stack.is_empty() {
+            self.pop_entry();
+        }
+    }
+
+    fn create_scene(&self, name: &str) -> Result<Box<dyn Scene>, SceneError> {
+        let Some(factory) = self.scenes.get(name) else {
+            return Err(SceneError::UnknownScene(String::from(name)));
+        };
+
+        Ok(factory())
+    }
+
+    pub fn take_scene_errors(&mut self) -> Vec<SceneError> {
+        std::mem::take(&mut self.scene_errors)
+    }
+
+    fn handle_scene_requests(&mut self) {
+        for request in self.scene_requests.take() {
+            let result = match request {
+                SceneRequest::Open(name) => self.open_scene_by_name(&name),
+                SceneRequest::OpenWithTransition(name, transition) => self.open_scene_by_name_with_transition(&name, transition),
+                SceneRequest::Push(name, push_mode) => self.push_scene_by_name(&name, push_mode),
+                SceneRequest::Pop => {
+                    self.pop_scene();
+                    Ok(())
+                }
+                SceneRequest::SaveState => {
+                    self.save_state();
+                    Ok(())
+                }
+                SceneRequest::LoadState => {
+                    self.load_state();
+                    Ok(())
+                }
+            };
+
+            if let Err(error) = result {
+                self.scene_errors.push(error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{
+            Arc, Mutex,
+            atomic::{AtomicU32, Ordering},
+        },
+        time::Duration,
+    };
+
+    use crate::engine::{
+        asyncable::AsyncableType,
+        clock::ManualClock,
+        components::world::World,
+        engine::{ActorId, Engine},
+        engine_config::EngineConfig,
+        input::input::{EmptyInput, Input},
+        scene::{PushMode, Scene, SceneError},
+        system::{Scheduler, Stage},
+        threading_provider::Thread,
+    };
+    use crate::scenes::pong::{pong_scene::PongScene, pong_state::PongState};
+
+    struct CountingScene {
+        fixed_ticks: Arc<AtomicU32>,
+    }
+
+    impl Scene for CountingScene {
+        fn init(&mut self, _world: &mut World) {}
+
+        fn tick(&mut self, _input: &Box<dyn Input>, _world: &mut World, _delta_time: f32) {}
+
+        fn fixed_tick(&mut self, _input: &Box<dyn Input>, _world: &mut World, _fixed_delta_time: f32) {
+            self.fixed_ticks.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_overlaps(&mut self, _overlaps: &HashMap<ActorId, Vec<ActorId>>, _world: &mut World, _delta_time: f32) {}
+    }
+
+    struct RecordingScene {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl RecordingScene {
+        fn record(&self, event: &str) {
+            self.log.lock().unwrap().push(format!("{}:{}", self.name, event));
+        }
+    }
+
+    impl Scene for RecordingScene {
+        fn init(&mut self, _world: &mut World) {
+            self.record("init");
+        }
+
+        fn tick(&mut self, _input: &Box<dyn Input>, _world: &mut World, _delta_time: f32) {}
+
+        fn on_overlaps(&mut self, _overlaps: &HashMap<ActorId, Vec<ActorId>>, _world: &mut World, _delta_time: f32) {}
+
+        fn on_pause(&mut self, _world: &mut World) {
+            self.record("pause");
+        }
+
+        fn on_resume(&mut self, _world: &mut World) {
+            self.record("resume");
+        }
+
+        fn on_focus(&mut self, _world: &mut World) {
+            self.record("focus");
+        }
+
+        fn on_blur(&mut self, _world: &mut World) {
+            self.record("blur");
+        }
+
+        fn on_exit(&mut self, _world: &mut World) {
+            self.record("exit");
+        }
+    }
+
+    fn create_recording_engine(log: &Arc<Mutex<Vec<String>>>) -> Engine {
+        let mut engine = Engine::new(Box::new(EmptyInput::new()), Box::new(ManualClock::new()), EngineConfig::default(), "a");
+        for name in ["a", "b", "c"] {
+            let log = log.clone();
+            engine.register_scene(name, Box::new(move || Box::new(RecordingScene { name, log: log.clone() })));
+        }
+        engine
+    }
+
+    fn take_log(log: &Arc<Mutex<Vec<String>>>) -> Vec<String> {
+        std::mem::take(&mut *log.lock().unwrap())
+    }
+
+    struct StageScene {
+        stages: Arc<Mutex<Vec<Stage>>>,
+    }
+
+    impl Scene for StageScene {
+        fn init(&mut self, _world: &mut World) {}
+
+        fn register_systems(&mut self, scheduler: &mut Scheduler) {
+            for stage in [Stage::Render, Stage::PostPhysics, Stage::Physics, Stage::Update, Stage::PreUpdate] {
+                let stages = self.stages.clone();
+                scheduler.add_system(stage, "record", Box::new(move |_, _| stages.lock().unwrap().push(stage)));
+            }
+        }
+
+        fn tick(&mut self, _input: &Box<dyn Input>, _world: &mut World, _delta_time: f32) {}
+
+        fn on_overlaps(&mut self, _overlaps: &HashMap<ActorId, Vec<ActorId>>, _world: &mut World, _delta_time: f32) {}
+    }
+
+    struct ImmediateThread;
+
+    impl Thread for ImmediateThread {
+        fn start(handle: Box<dyn Fn() + Send>) -> Self {
+            handle();
+            Self
+        }
+
+        fn stop(&self) {}
+
+        fn sleep_for(_ms: u64) {}
+    }
+
+    fn create_engine(fixed_ticks: Arc<AtomicU32>, clock: ManualClock) -> Engine {
+        let mut engine = Engine::new(Box::new(EmptyInput::new()), Box::new(clock), EngineConfig::default(), "counting");
+        engine.register_scene(
+            "counting",
+            Box::new(move || {
+                Box::new(CountingScene {
+                    fixed_ticks: fixed_ticks.clone(),
+                })
+            }),
+        );
+        engine
+    }
+
+    #[test]
+    fn step_with_manual_clock_runs_fixed_steps() {
+        let fixed_ticks = Arc::new(AtomicU32::new(0));
+        let clock = ManualClock::new();
+        let mut engine = create_engine(fixed_ticks.clone(), clock.clone());
+
+        engine.step_with_clock();
+        assert_eq!(fixed_ticks.load(Ordering::Relaxed), 0);
+
+        clock.advance(Duration::from_millis(51));
+        let screen = engine.step_with_clock();
+        assert_eq!(fixed_ticks.load(Ordering::Relaxed), 3);
+        assert_eq!((screen.width, screen.height), (64, 64));
+    }
+
+    #[test]
+    fn unknown_scene_names_are_reported() {
+        let mut engine = Engine::new(Box::new(EmptyInput::new()), Box::new(ManualClock::new()), EngineConfig::default(), "missing");
+        assert_eq!(engine.run::<ImmediateThread>(Arc::new(|_| {})), Err(SceneError::UnknownScene(String::from("missing"))));
+
+        engine.step(0.1);
+        assert_eq!(engine.take_scene_errors(), vec![SceneError::UnknownScene(String::from("missing"))]);
+        assert_eq!(engine.open_scene_by_name("missing"), Err(SceneError::UnknownScene(String::from("missing"))));
+    }
+
+    #[test]
+    fn unknown_scene_requests_are_reported() {
+        let mut engine = create_engine(Arc::new(AtomicU32::new(0)), ManualClock::new());
+        engine.step(0.0);
+        assert!(engine.take_scene_errors().is_empty());
+
+        engine.scene_stack[0].world.scene_requests().open_scene("missing");
+        engine.scene_stack[0].world.scene_requests().push_scene("counting", PushMode::TickBelow);
+        engine.step(0.0);
+
+        assert_eq!(engine.take_scene_errors(), vec![SceneError::UnknownScene(String::from("missing"))]);
+        assert_eq!(engine.scene_stack.len(), 2);
+    }
+
+    #[test]
+    fn load_state_restores_pong_paddles() {
+        let mut engine = Engine::new(Box::new(EmptyInput::new()), Box::new(ManualClock::new()), EngineConfig::default(), "pong");
+        engine.register_scene("pong", Box::new(|| Box::new(PongScene::new())));
+        engine.step(0.05);
+        engine.save_state();
+
+        let world = &mut engine.scene_stack.last_mut().unwrap().world;
+        let paddles = std::mem::take(&mut world.get_mut_resource::<PongState>().unwrap().paddle);
+        paddles.iter().flatten().for_each(|f| world.remove_actor(f));
+
+        engine.load_state();
+        let world = &engine.scene_stack.last().unwrap().world;
+        let paddles = world.get_resource::<PongState>().unwrap().paddle;
+        assert!(paddles.iter().all(|f| f.is_some_and(|g| world.is_alive(&g))));
+    }
+
+    #[test]
+    fn engines_can_be_created_repeatedly() {
+        for _ in 0..2 {
+            let fixed_ticks = Arc::new(AtomicU32::new(0));
+            let clock = ManualClock::new();
+            let mut engine = create_engine(fixed_ticks.clone(), clock.clone());
+            clock.advance(Duration::from_millis(20));
+            engine.step_with_clock();
+            assert_eq!(fixed_ticks.load(Ordering::Relaxed), 1);
+        }
+    }
+
+    #[test]
+    fn quit_signal_ends_run() {
+        let mut engine = create_engine(Arc::new(AtomicU32::new(0)), ManualClock::new());
+        let quit_signal = engine.get_quit_signal();
+        let frames = Arc::new(AtomicU32::new(0));
+        let counted_frames = frames.clone();
+
+        let result = engine.run::<ImmediateThread>(Arc::new(move |_| {
+            if counted_frames.fetch_add(1, Ordering::Relaxed) == 2 {
+                quit_signal.store(true, Ordering::Relaxed);
+            }
+        }));
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(frames.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn queues_stay_with_their_engine() {
+        let mut first = create_engine(Arc::new(AtomicU32::new(0)), ManualClock::new());
+        let mut second = create_engine(Arc::new(AtomicU32::new(0)), ManualClock::new());
+        first.step(0.0);
+        second.step(0.0);
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let counted_calls = calls.clone();
+        first.scene_stack[0].world.add_asyncable(
+            Box::new(move |_, _| {
+                counted_calls.fetch_add(1, Ordering::Relaxed);
+            }),
+            0.0,
+            AsyncableType::Timeout,
+        );
+        first.get_scene_requests().push_scene("counting", PushMode::TickBelow);
+
+        second.step(0.0);
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+        assert_eq!(second.scene_stack.len(), 1);
+
+        first.step(0.0);
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        assert_eq!(first.scene_stack.len(), 2);
+    }
+
+    #[test]
+    fn scene_stack_changes_call_lifecycle_hooks_in_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut engine = create_recording_engine(&log);
+
+        engine.step(0.0);
+        assert_eq!(take_log(&log), ["a:init", "a:focus"]);
+
+        engine.push_scene_by_name("b", PushMode::PauseBelow).unwrap();
+        assert_eq!(take_log(&log), ["b:init", "a:blur", "a:pause", "b:focus"]);
+
+        engine.pop_scene();
+        assert_eq!(take_log(&log), ["b:exit", "a:resume", "a:focus"]);
+
+        engine.push_scene_by_name("c", PushMode::TickBelow).unwrap();
+        assert_eq!(take_log(&log), ["c:init", "a:blur", "c:focus"]);
+
+        engine.open_scene_by_name("b").unwrap();
+        assert_eq!(take_log(&log), ["c:exit", "a:exit", "b:init", "b:focus"]);
+    }
+
+    #[test]
+    fn run_exits_remaining_scenes_on_quit() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut engine = create_recording_engine(&log);
+        engine.step(0.0);
+        engine.push_scene_by_name("b", PushMode::TickBelow).unwrap();
+        take_log(&log);
+
+        let quit_signal = engine.get_quit_signal();
+        engine.run::<ImmediateThread>(Arc::new(move |_| quit_signal.store(true, Ordering::Relaxed))).unwrap();
+
+        assert_eq!(take_log(&log), ["b:exit", "a:exit"]);
+        assert!(engine.scene_stack.is_empty());
+    }
+
+    #[test]
+    fn stages_run_in_declared_order() {
+        let stages = Arc::new(Mutex::new(Vec::new()));
+        let scene_stages = stages.clone();
+        let mut engine = Engine::new(Box::new(EmptyInput::new()), Box::new(ManualClock::new()), EngineConfig::default(), "stages");
+        engine.register_scene("stages", Box::new(move || Box::new(StageScene { stages: scene_stages.clone() })));
+
+        engine.step(engine.get_fixed_delta_time());
+
+        assert_eq!(*stages.lock().unwrap(), [Stage::PreUpdate, Stage::Update, Stage::Physics, Stage::PostPhysics, Stage::Render]);
     }
 }
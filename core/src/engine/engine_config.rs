@@ -0,0 +1,24 @@
+#[derive(Clone)]
+pub struct EngineConfig {
+    pub width: u16,
+    pub height: u16,
+    pub fixed_update_rate: f32,
+    pub target_frame_ms: u64,
+}
+
+impl EngineConfig {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            fixed_update_rate: 60.0,
+            target_frame_ms: 33,
+        }
+    }
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        EngineConfig::new(64, 64)
+    }
+}
@@ -21,39 +21,33 @@ impl EmptyInput {
 }
 
 impl Input for EmptyInput {
-    fn update(&mut self, _: f32) {
-        todo!()
-    }
+    fn update(&mut self, _: f32) {}
 
-    fn late_update(&mut self, _: f32) {
-        todo!()
-    }
+    fn late_update(&mut self, _: f32) {}
 
     fn is_key_down(&self, _: Key) -> bool {
-        todo!()
+        false
     }
 
     fn is_any_key_down(&self) -> bool {
-        todo!()
+        false
     }
 
     fn is_key_up(&self, _: Key) -> bool {
-        todo!()
+        false
     }
 
     fn is_any_key_up(&self) -> bool {
-        todo!()
+        false
     }
 
     fn is_key_press(&self, _: Key) -> bool {
-        todo!()
+        false
     }
 
     fn is_any_key_press(&self) -> bool {
-        todo!()
+        false
     }
 
-    fn clear(&mut self) {
-        todo!()
-    }
+    fn clear(&mut self) {}
 }
@@ -1,34 +1,66 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-#[derive(Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Matrix<T: Clone> {
-    pub width: u8,
-    pub height: u8,
+    pub width: usize,
+    pub height: usize,
     pub data: Vec<T>,
 }
 
 impl<T: Clone> Matrix<T> {
-    pub fn new(width: u8, height: u8, init: T) -> Self {
+    pub fn new(width: usize, height: usize, init: T) -> Self {
         Self {
             width,
             height,
-            data: vec![init; (width as usize * height as usize) as usize],
+            data: vec![init; width * height],
         }
     }
 
-    pub fn get(&self, x: u8, y: u8) -> &T {
-        if x > self.width {
+    pub fn get(&self, x: usize, y: usize) -> &T {
+        if x >= self.width {
             panic!("Matrix::at: x outside of (0, {}): {}", self.width, x)
         }
-        if y > self.height {
+        if y >= self.height {
             panic!("Matrix::at: y outside of (0, {}): {}", self.height, y)
         }
-        &self.data[(y as usize * self.width as usize + x as usize) as usize]
+        &self.data[y * self.width + x]
     }
 
-    pub fn set(&mut self, x: u8, y: u8, to: T) -> &mut Self {
+    pub fn get_signed(&self, x: i32, y: i32) -> Option<&T> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        Some(&self.data[y as usize * self.width + x as usize])
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, to: T) -> &mut Self {
         if x < self.width && y < self.height {
-            self.data[(y as u16 * self.width as u16 + x as u16) as usize] = to;
+            self.data[y * self.width + x] = to;
+        }
+        self
+    }
+
+    pub fn set_signed(&mut self, x: i32, y: i32, to: T) -> &mut Self {
+        if x >= 0 && y >= 0 {
+            self.set(x as usize, y as usize, to);
+        }
+        self
+    }
+
+    pub fn blit(&mut self, other: &Matrix<T>, origin_x: i32, origin_y: i32) -> &mut Self {
+        let from_x = (-(origin_x as i64)).clamp(0, other.width as i64) as usize;
+        let from_y = (-(origin_y as i64)).clamp(0, other.height as i64) as usize;
+        let to_x = (self.width as i64 - origin_x as i64).clamp(0, other.width as i64) as usize;
+        let to_y = (self.height as i64 - origin_y as i64).clamp(0, other.height as i64) as usize;
+
+        for y in from_y..to_y {
+            for x in from_x..to_x {
+                let target_x = (x as i64 + origin_x as i64) as usize;
+                let target_y = (y as i64 + origin_y as i64) as usize;
+                self.data[target_y * self.width + target_x] = other.data[y * other.width + x].clone();
+            }
         }
         self
     }
@@ -38,7 +70,7 @@ impl<T: Clone> Matrix<T> {
     }
 
     pub fn fill(&mut self, to: T) {
-        self.data = vec![to; (self.width * self.height) as usize];
+        self.data = vec![to; self.width * self.height];
     }
 
     // TODO: do in-place. swapping pixels
@@ -52,15 +84,15 @@ impl<T: Clone> Matrix<T> {
         let new_width = (old_width * cos_abs + old_width * sin_abs).ceil();
         let new_height = (old_height * cos_abs + old_height * sin_abs).ceil();
 
-        let mut rotated = Matrix::<T>::new(new_width as u8, new_height as u8, background);
+        let mut rotated = Matrix::<T>::new(new_width as usize, new_height as usize, background);
 
         let old_cx = old_width / 2.0;
         let old_cy = old_height / 2.0;
         let new_cx = new_width / 2.0;
         let new_cy = new_height / 2.0;
 
-        for x in 0..(old_width as u8) {
-            for y in 0..(old_height as u8) {
+        for x in 0..(old_width as usize) {
+            for y in 0..(old_height as usize) {
                 let dx = x as f32 - old_cx;
                 let dy = y as f32 - old_cy;
 
@@ -80,15 +112,15 @@ impl<T: Clone> Matrix<T> {
     pub fn scale(&mut self, factor: f32, background: T) {
         let old_width = self.width;
         let old_height = self.height;
-        let new_width = (self.width as f32 * factor).round() as u8;
-        let new_height = (self.height as f32 * factor).round() as u8;
+        let new_width = (self.width as f32 * factor).round() as usize;
+        let new_height = (self.height as f32 * factor).round() as usize;
 
         let mut scaled = Matrix::<T>::new(new_width, new_height, background);
 
         for x in 0..new_width {
             for y in 0..new_height {
-                let src_x = (x as f32 / factor).floor() as u8;
-                let src_y = (y as f32 / factor).floor() as u8;
+                let src_x = (x as f32 / factor).floor() as usize;
+                let src_y = (y as f32 / factor).floor() as usize;
 
                 if src_x < old_width && src_y < old_height {
                     scaled.set(x, y, self.get(src_x, src_y).clone());
@@ -141,3 +173,67 @@ impl<T: Default + Clone + PartialEq + Display> fmt::Display for Matrix<T> {
         write!(f, "{return_value}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::matrix::Matrix;
+
+    fn numbered(width: usize, height: usize) -> Matrix<u8> {
+        Matrix {
+            width,
+            height,
+            data: (1..=(width * height) as u8).collect(),
+        }
+    }
+
+    fn written(matrix: &Matrix<u8>) -> Vec<(usize, usize, u8)> {
+        (0..matrix.height)
+            .flat_map(|y| (0..matrix.width).map(move |x| (x, y)))
+            .filter_map(|(x, y)| Some(*matrix.get(x, y)).filter(|f| *f != 0).map(|f| (x, y, f)))
+            .collect()
+    }
+
+    #[test]
+    fn blit_clips_right_and_bottom_edges() {
+        let mut target = Matrix::new(4, 4, 0);
+        target.blit(&numbered(2, 2), 3, 3);
+        assert_eq!(written(&target), [(3, 3, 1)]);
+
+        let mut target = Matrix::new(4, 4, 0);
+        target.blit(&numbered(2, 2), 3, 0);
+        assert_eq!(written(&target), [(3, 0, 1), (3, 1, 3)]);
+    }
+
+    #[test]
+    fn blit_clips_left_and_top_edges() {
+        let mut target = Matrix::new(4, 4, 0);
+        target.blit(&numbered(2, 2), -1, -1);
+        assert_eq!(written(&target), [(0, 0, 4)]);
+
+        let mut target = Matrix::new(4, 4, 0);
+        target.blit(&numbered(2, 2), 0, -1);
+        assert_eq!(written(&target), [(0, 0, 3), (1, 0, 4)]);
+    }
+
+    #[test]
+    fn blit_ignores_origins_far_outside() {
+        let mut target = Matrix::new(4, 4, 0);
+        for (x, y) in [(i32::MIN, i32::MIN), (i32::MIN, 0), (0, i32::MIN), (i32::MAX, i32::MAX), (-2, 0), (4, 0)] {
+            target.blit(&numbered(2, 2), x, y);
+        }
+        assert!(written(&target).is_empty());
+    }
+
+    #[test]
+    fn blit_clips_source_larger_than_target() {
+        let source = numbered(6, 6);
+        let mut target = Matrix::new(4, 4, 0);
+        target.blit(&source, -1, -2);
+
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(target.get(x, y), source.get(x + 1, y + 2));
+            }
+        }
+    }
+}
@@ -3,9 +3,16 @@ pub mod v2;
 pub mod color;
 pub mod color_matrix;
 pub mod engine;
+pub mod engine_config;
 pub mod threading_provider;
+pub mod clock;
+pub mod time_control;
 pub mod actor;
 pub mod scene;
 pub mod input;
 pub mod components;
 pub mod asyncable;
+pub mod transition;
+pub mod system;
+pub mod systems;
+pub mod commands;
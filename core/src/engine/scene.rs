@@ -1,11 +1,101 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
-use crate::engine::{components::world::World, engine::ActorId, input::input::Input};
+use crate::engine::{
+    components::{contact::{CollisionEvent, Contact, ContactTracker}, world::World},
+    engine::ActorId,
+    input::input::Input,
+    system::Scheduler,
+    transition::Transition,
+};
+
+pub type SceneId = u16;
+pub type SceneFactory = Box<dyn Fn() -> Box<dyn Scene> + Send + Sync + 'static>;
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum PushMode {
+    PauseBelow,
+    TickBelow,
+}
+
+pub enum SceneRequest {
+    Open(String),
+    OpenWithTransition(String, Transition),
+    Push(String, PushMode),
+    Pop,
+    SaveState,
+    LoadState,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum SceneError {
+    UnknownScene(String),
+}
+
+pub struct SceneEntry {
+    pub id: SceneId,
+    pub scene: Box<dyn Scene>,
+    pub world: World,
+    pub scheduler: Scheduler,
+    pub contact_tracker: ContactTracker,
+    pub push_mode: PushMode,
+}
+
+#[derive(Clone, Default)]
+pub struct SceneRequests {
+    requests: Arc<Mutex<Vec<SceneRequest>>>,
+}
+
+impl SceneRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open_scene(&self, name: &str) {
+        self.requests.lock().unwrap().push(SceneRequest::Open(String::from(name)));
+    }
+
+    pub fn open_scene_with_transition(&self, name: &str, transition: Transition) {
+        self.requests.lock().unwrap().push(SceneRequest::OpenWithTransition(String::from(name), transition));
+    }
+
+    pub fn push_scene(&self, name: &str, push_mode: PushMode) {
+        self.requests.lock().unwrap().push(SceneRequest::Push(String::from(name), push_mode));
+    }
+
+    pub fn pop_scene(&self) {
+        self.requests.lock().unwrap().push(SceneRequest::Pop);
+    }
+
+    pub fn save_state(&self) {
+        self.requests.lock().unwrap().push(SceneRequest::SaveState);
+    }
+
+    pub fn load_state(&self) {
+        self.requests.lock().unwrap().push(SceneRequest::LoadState);
+    }
+
+    pub fn take(&self) -> Vec<SceneRequest> {
+        std::mem::take(&mut *self.requests.lock().unwrap())
+    }
+}
 
 pub trait Scene {
     fn init(&mut self, world: &mut World);
+    fn register_systems(&mut self, _scheduler: &mut Scheduler) {}
     fn tick(&mut self, input: &Box<dyn Input>, world: &mut World, delta_time: f32);
-    fn on_overlaps(&mut self, overlaps: &HashMap<ActorId, Vec<ActorId>>, world: &mut World, delta_time: f32);
+    fn fixed_tick(&mut self, _input: &Box<dyn Input>, _world: &mut World, _fixed_delta_time: f32) {}
+    fn on_overlaps(&mut self, _overlaps: &HashMap<ActorId, Vec<ActorId>>, _world: &mut World, _delta_time: f32) {}
+    fn on_collisions(&mut self, _events: &[CollisionEvent], _world: &mut World, _delta_time: f32) {}
+    fn on_contacts(&mut self, _contacts: &[Contact], _world: &mut World, _delta_time: f32) {}
+    fn late_tick(&mut self, _world: &mut World, _delta_time: f32) {}
+    fn on_pause(&mut self, _world: &mut World) {}
+    fn on_resume(&mut self, _world: &mut World) {}
+    fn on_focus(&mut self, _world: &mut World) {}
+    fn on_blur(&mut self, _world: &mut World) {}
+    fn on_exit(&mut self, _world: &mut World) {}
 }
 
 pub struct EmptyScene;
@@ -0,0 +1,56 @@
+use crate::engine::components::world::World;
+
+pub type SystemFunction = Box<dyn FnMut(&mut World, f32) + Send + Sync + 'static>;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Stage {
+    PreUpdate,
+    Update,
+    Physics,
+    PostPhysics,
+    Render,
+}
+
+pub struct SystemEntry {
+    pub name: String,
+    pub stage: Stage,
+    pub function: SystemFunction,
+}
+
+pub struct Scheduler {
+    systems: Vec<SystemEntry>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self { systems: Vec::new() }
+    }
+
+    pub fn add_system(&mut self, stage: Stage, name: &str, function: SystemFunction) {
+        self.systems.push(SystemEntry {
+            name: String::from(name),
+            stage,
+            function,
+        });
+    }
+
+    pub fn remove_system(&mut self, name: &str) {
+        self.systems.retain(|f| f.name != name);
+    }
+
+    pub fn has_system(&self, name: &str) -> bool {
+        self.systems.iter().any(|f| f.name == name)
+    }
+
+    pub fn run_stage(&mut self, stage: Stage, world: &mut World, delta_time: f32) {
+        for system in self.systems.iter_mut().filter(|f| f.stage == stage) {
+            (system.function)(world, delta_time);
+        }
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
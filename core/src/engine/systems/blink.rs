@@ -0,0 +1,22 @@
+use crate::engine::{color_matrix::ColorMatrix, components::blink::Blink, system::SystemFunction};
+
+pub const BLINK_SYSTEM: &str = "blink";
+
+pub fn blink_system() -> SystemFunction {
+    Box::new(|world, delta_time| {
+        for actor_id in world.query::<(Blink,)>() {
+            let blink = world.get_mut::<Blink>(&actor_id).unwrap();
+            blink.timer += delta_time;
+            if blink.timer < blink.interval {
+                continue;
+            }
+
+            blink.timer = 0.0;
+            if let Some(render) = blink.hidden_render.take() {
+                world.insert(&actor_id, render);
+            } else if let Some(render) = world.remove::<ColorMatrix>(&actor_id) {
+                world.get_mut::<Blink>(&actor_id).unwrap().hidden_render = Some(render);
+            }
+        }
+    })
+}
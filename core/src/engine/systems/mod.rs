@@ -0,0 +1,2 @@
+pub mod velocity;
+pub mod blink;
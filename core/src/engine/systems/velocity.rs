@@ -0,0 +1,14 @@
+use crate::engine::{
+    components::{physics::Physics, transform::Transform},
+    system::SystemFunction,
+};
+
+pub const VELOCITY_SYSTEM: &str = "velocity";
+
+pub fn velocity_system() -> SystemFunction {
+    Box::new(|world, delta_time| {
+        world.query_mut::<Transform, (Physics,)>(|_, transform, (physics,)| {
+            transform.center += &physics.velocity * delta_time;
+        });
+    })
+}
@@ -0,0 +1,43 @@
+pub struct TimeControl {
+    pub time_scale: f32,
+    pub is_paused: bool,
+    pub frames_to_advance: u32,
+}
+
+impl TimeControl {
+    pub fn new() -> Self {
+        Self {
+            time_scale: 1.0,
+            is_paused: false,
+            frames_to_advance: 0,
+        }
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.is_paused = !self.is_paused;
+    }
+
+    pub fn advance_frame(&mut self) {
+        self.is_paused = true;
+        self.frames_to_advance += 1;
+    }
+
+    pub fn scale_delta_time(&mut self, delta_time: f32, fixed_delta_time: f32) -> f32 {
+        if !self.is_paused {
+            return delta_time * self.time_scale;
+        }
+
+        if self.frames_to_advance > 0 {
+            self.frames_to_advance -= 1;
+            return fixed_delta_time;
+        }
+
+        0.0
+    }
+}
+
+impl Default for TimeControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
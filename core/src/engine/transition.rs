@@ -0,0 +1,119 @@
+use crate::engine::{color::Color, color_matrix::ColorMatrix, scene::SceneEntry};
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum TransitionKind {
+    FadeToBlack,
+    WipeHorizontal,
+    WipeVertical,
+    Dissolve,
+    SlideLeft,
+    SlideRight,
+    SlideUp,
+    SlideDown,
+}
+
+#[derive(Clone, Copy)]
+pub struct Transition {
+    pub kind: TransitionKind,
+    pub duration: f32,
+}
+
+pub struct TransitionInProgress {
+    pub transition: Transition,
+    pub outgoing: Vec<SceneEntry>,
+    pub timer: f32,
+}
+
+impl Transition {
+    pub fn new(kind: TransitionKind, duration: f32) -> Self {
+        Self { kind, duration }
+    }
+
+    pub fn mix(&self, from: &ColorMatrix, to: &ColorMatrix, progress: f32) -> ColorMatrix {
+        let progress = progress.clamp(0.0, 1.0);
+        let width = to.width;
+        let height = to.height;
+        let mut mixed = ColorMatrix::new(width, height, Color::none());
+
+        for x in 0..width {
+            for y in 0..height {
+                let color = match self.kind {
+                    TransitionKind::FadeToBlack => {
+                        if progress < 0.5 {
+                            Transition::darken(from.get(x, y), 1.0 - progress * 2.0)
+                        } else {
+                            Transition::darken(to.get(x, y), progress * 2.0 - 1.0)
+                        }
+                    }
+                    TransitionKind::WipeHorizontal => {
+                        if (x as f32) < progress * width as f32 {
+                            to.get(x, y).clone()
+                        } else {
+                            from.get(x, y).clone()
+                        }
+                    }
+                    TransitionKind::WipeVertical => {
+                        if (y as f32) < progress * height as f32 {
+                            to.get(x, y).clone()
+                        } else {
+                            from.get(x, y).clone()
+                        }
+                    }
+                    TransitionKind::Dissolve => {
+                        if Transition::dissolve_threshold(x, y) < progress {
+                            to.get(x, y).clone()
+                        } else {
+                            from.get(x, y).clone()
+                        }
+                    }
+                    TransitionKind::SlideLeft => Transition::slide(from, to, x as i32 + Transition::offset(progress, width), y as i32, width, true),
+                    TransitionKind::SlideRight => Transition::slide(to, from, x as i32 + Transition::offset(1.0 - progress, width), y as i32, width, true),
+                    TransitionKind::SlideUp => Transition::slide(from, to, x as i32, y as i32 + Transition::offset(progress, height), height, false),
+                    TransitionKind::SlideDown => Transition::slide(to, from, x as i32, y as i32 + Transition::offset(1.0 - progress, height), height, false),
+                };
+
+                mixed.set(x, y, color);
+            }
+        }
+
+        mixed
+    }
+
+    fn darken(color: &Color, factor: f32) -> Color {
+        Color::new(
+            (color.r as f32 * factor).round() as u8,
+            (color.g as f32 * factor).round() as u8,
+            (color.b as f32 * factor).round() as u8,
+            color.a,
+        )
+    }
+
+    fn dissolve_threshold(x: usize, y: usize) -> f32 {
+        let mut hash = (x as u32).wrapping_mul(73_856_093) ^ (y as u32).wrapping_mul(19_349_663);
+        hash ^= hash >> 13;
+        hash = hash.wrapping_mul(0x5bd1_e995);
+        hash ^= hash >> 15;
+        (hash % 1000) as f32 / 1000.0
+    }
+
+    fn offset(progress: f32, edge: usize) -> i32 {
+        (progress * edge as f32).round() as i32
+    }
+
+    fn slide(first: &ColorMatrix, second: &ColorMatrix, x: i32, y: i32, edge: usize, is_horizontal: bool) -> Color {
+        let edge = edge as i32;
+        let position = if is_horizontal { x } else { y };
+
+        let (source, position) = if position < edge { (first, position) } else { (second, position - edge) };
+
+        if position >= edge {
+            return Color::none();
+        }
+
+        if is_horizontal {
+            source.get(position as usize, y as usize).clone()
+        } else {
+            source.get(x as usize, position as usize).clone()
+        }
+    }
+}
@@ -1,8 +1,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use core::f32::consts::PI;
+use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Default, Clone)]
+#[derive(PartialEq, Default, Clone, Serialize, Deserialize)]
 pub struct V2 {
     pub x: f32,
     pub y: f32,
@@ -53,6 +54,10 @@ impl V2 {
         (&self.x.powi(2) + self.y.powi(2)).sqrt()
     }
 
+    pub fn dot(&self, other: &V2) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
     pub fn floor(&self) -> Self {
         Self {
             x: self.x.floor(),
@@ -74,10 +79,28 @@ impl V2 {
         }
     }
 
+    pub fn lerp(&self, to: &V2, t: f32) -> Self {
+        Self {
+            x: self.x + (to.x - self.x) * t,
+            y: self.y + (to.y - self.y) * t,
+        }
+    }
+
     pub fn norm(&self) -> Self {
         self / self.mag()
     }
 
+    pub fn rotated(&self, degrees: f32) -> Self {
+        let rad = (degrees * PI) / 180.0;
+        let cos = rad.cos();
+        let sin = rad.sin();
+
+        Self {
+            x: cos * self.x - sin * self.y,
+            y: sin * self.x + cos * self.y,
+        }
+    }
+
     pub fn rotate_around(&mut self, pivot: &V2, degrees: &f32) -> Self {
         let rad = (degrees * PI) / 180.0;
         let dx = self.x - pivot.x;
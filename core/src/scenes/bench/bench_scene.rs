@@ -0,0 +1,136 @@
+use rand::Rng;
+use std::collections::HashMap;
+
+use crate::engine::{
+    actor::rectangle_actor::create_rectangle_actor,
+    clock::{Clock, StdClock},
+    color::Color,
+    color_matrix::ColorMatrix,
+    components::{
+        collider::{Collider, ColliderType},
+        physics::Physics,
+        world::World,
+    },
+    engine::ActorId,
+    input::input::Input,
+    scene::Scene,
+    system::{Scheduler, Stage},
+    systems::velocity::{VELOCITY_SYSTEM, velocity_system},
+    v2::V2,
+};
+
+const MICROS_PER_PIXEL: f32 = 50.0;
+const AVERAGE_WEIGHT: f32 = 0.05;
+
+pub struct BenchScene {
+    bullet_count: usize,
+    bullet_speed: f32,
+    bullets: Vec<ActorId>,
+    bars: [Option<ActorId>; 2],
+    average_micros: [f32; 2],
+    clock: StdClock,
+}
+
+impl BenchScene {
+    pub fn new(bullet_count: usize) -> Self {
+        Self {
+            bullet_count,
+            bullet_speed: 10.0,
+            bullets: Vec::new(),
+            bars: [None, None],
+            average_micros: [0.0, 0.0],
+            clock: StdClock::new(),
+        }
+    }
+
+    fn measure(&self, detect: fn(&World) -> HashMap<ActorId, Vec<ActorId>>, world: &World) -> (f32, HashMap<ActorId, Vec<ActorId>>) {
+        let start = self.clock.now();
+        let overlaps = detect(world);
+        ((self.clock.now() - start).as_micros() as f32, overlaps)
+    }
+
+    fn wrap_bullets(&self, world: &mut World) {
+        let screen_size = world.get_screen_size();
+        for bullet in &self.bullets {
+            let Some(transform) = world.get_mut_transform(bullet) else {
+                continue;
+            };
+            let wrapped = V2::new(transform.center.x.rem_euclid(screen_size.x), transform.center.y.rem_euclid(screen_size.y));
+            if wrapped != transform.center {
+                transform.teleport(wrapped);
+            }
+        }
+    }
+
+    fn update_bar(bar: &ActorId, world: &mut World, micros: f32, row: f32, color: Color) {
+        let screen_width = world.get_screen_width() as f32;
+        let width = (micros / MICROS_PER_PIXEL).clamp(1.0, screen_width).round();
+        if let Some(transform) = world.get_mut_transform(bar) {
+            transform.teleport(V2::new(width / 2.0, row + 0.5));
+            transform.size = V2::new(width, 1.0);
+        }
+        if let Some(render) = world.get_mut_render(bar) {
+            *render = ColorMatrix::new(width as usize, 1, color);
+        }
+    }
+}
+
+impl Scene for BenchScene {
+    fn init(&mut self, world: &mut World) {
+        let screen_size = world.get_screen_size();
+        let mut rng = rand::thread_rng();
+
+        self.bullets = (0..self.bullet_count)
+            .map(|_| {
+                let bullet = create_rectangle_actor(
+                    world,
+                    V2::new(rng.gen_range(0.0..screen_size.x), rng.gen_range(0.0..screen_size.y)),
+                    V2::one(),
+                    Color::white(),
+                    Some(ColliderType::Overlapping),
+                    Some(String::from("bullet")),
+                );
+                let angle: f32 = rng.gen_range(0.0..std::f32::consts::TAU);
+                world.insert(&bullet, Physics::with_velocity(V2::new(angle.cos(), angle.sin()) * self.bullet_speed));
+                bullet
+            })
+            .collect();
+
+        self.bars = [
+            Some(create_rectangle_actor(world, V2::new(0.5, 0.5), V2::one(), Color::green(), None, Some(String::from("grid bar")))),
+            Some(create_rectangle_actor(world, V2::new(0.5, 1.5), V2::one(), Color::red(), None, Some(String::from("brute force bar")))),
+        ];
+    }
+
+    fn register_systems(&mut self, scheduler: &mut Scheduler) {
+        scheduler.add_system(Stage::Physics, VELOCITY_SYSTEM, velocity_system());
+    }
+
+    fn tick(&mut self, _input: &Box<dyn Input>, world: &mut World, _delta_time: f32) {
+        let (grid_micros, grid_overlaps) = self.measure(Collider::detect_overlaps, world);
+        let (brute_force_micros, brute_force_overlaps) = self.measure(Collider::detect_overlaps_brute_force, world);
+        debug_assert!(grid_overlaps == brute_force_overlaps, "broad phase diverged from brute force");
+
+        for (average, micros) in self.average_micros.iter_mut().zip([grid_micros, brute_force_micros]) {
+            *average += (micros - *average) * AVERAGE_WEIGHT;
+        }
+
+        if let [Some(grid_bar), Some(brute_force_bar)] = self.bars {
+            BenchScene::update_bar(&grid_bar, world, self.average_micros[0], 0.0, Color::green());
+            BenchScene::update_bar(&brute_force_bar, world, self.average_micros[1], 1.0, Color::red());
+        }
+    }
+
+    fn fixed_tick(&mut self, _input: &Box<dyn Input>, world: &mut World, _delta_time: f32) {
+        self.wrap_bullets(world);
+    }
+
+    fn on_overlaps(&mut self, overlaps: &HashMap<ActorId, Vec<ActorId>>, world: &mut World, _delta_time: f32) {
+        for bullet in &self.bullets {
+            let color = if overlaps.contains_key(bullet) { Color::blue() } else { Color::white() };
+            if let Some(render) = world.get_mut_render(bullet) {
+                render.data.fill(color);
+            }
+        }
+    }
+}
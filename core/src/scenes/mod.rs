@@ -1 +1,2 @@
 pub mod pong;
+pub mod bench;
@@ -1,2 +1,3 @@
 pub mod pong_scene;
 pub mod masks;
+pub mod pong_state;
@@ -1,55 +1,47 @@
 use rand::Rng;
-use std::collections::HashMap;
 
 use crate::engine::{
     actor::rectangle_actor::create_rectangle_actor,
     color::Color,
-    components::{collider::ColliderType, world::World},
-    engine::{ActorId, SCREEN_SIZE},
+    components::{
+        collider::{Collider, ColliderPart, ColliderShape, ColliderType},
+        contact::{CollisionEvent, CollisionPhase},
+        physics::Physics,
+        world::World,
+    },
+    engine::ActorId,
     input::{input::Input, key::Key},
     scene::Scene,
+    system::{Scheduler, Stage},
+    systems::velocity::{VELOCITY_SYSTEM, velocity_system},
     v2::V2,
 };
+use crate::scenes::pong::pong_state::PongState;
 
 pub struct PongScene {
-    score: [u8; 2],
-    paddle: [Option<ActorId>; 2],
-    score_zone: [Option<ActorId>; 2],
-    ball: Option<ActorId>,
     paddle_speed: f32,
     max_bounce_speed: f32,
     original_ball_speed: f32,
-    ball_speed: V2,
-    ball_speed_multiplier: f32,
     size_factor: f32,
-    can_score: bool,
-    can_collide: [bool; 2],
     can_bounce: bool,
-    do_play: bool,
 }
 
 impl PongScene {
     pub fn new() -> Self {
         Self {
-            score: [0, 0],
-            paddle: [None, None],
-            score_zone: [None, None],
-            ball: None,
             paddle_speed: 15.0,
             max_bounce_speed: 0.03,
             original_ball_speed: 7.0,
-            ball_speed: V2::one(),
-            ball_speed_multiplier: 1.0,
-            size_factor: SCREEN_SIZE as f32 / 32.0,
-            can_score: true,
-            can_collide: [true, true],
+            size_factor: 1.0,
             can_bounce: true,
-            do_play: true,
         }
     }
 
     fn move_paddle(paddle: &ActorId, world: &mut World, delta: f32) {
-        let transform = world.get_mut_transform(paddle).unwrap();
+        let screen_width = world.get_screen_width() as f32;
+        let Some(transform) = world.get_mut_transform(paddle) else {
+            return;
+        };
         let diff = V2::right() * delta;
         transform.center = &transform.center + &diff;
         let left = transform.center.x - transform.size.x / 2.0;
@@ -57,86 +49,97 @@ impl PongScene {
         if left < 0.0 {
             transform.center = V2::new(transform.size.x / 2.0, transform.center.y);
         }
-        if right > SCREEN_SIZE as f32 {
-            transform.center = V2::new(SCREEN_SIZE as f32 - transform.size.x / 2.0, transform.center.y);
+        if right > screen_width {
+            transform.center = V2::new(screen_width - transform.size.x / 2.0, transform.center.y);
         }
     }
 
-    fn bounce_off_paddle(&mut self, overlaps: &HashMap<u16, Vec<u16>>, world: &mut World) {
+    fn bounce_off_paddle(&mut self, event: &CollisionEvent, world: &mut World) {
+        let state = world.get_resource::<PongState>().unwrap();
+        let (paddles, ball) = (state.paddle, state.ball.unwrap());
         for i in 0..2 {
-            if overlaps.contains_key(&self.ball.unwrap()) && overlaps[&self.ball.unwrap()].contains(&self.paddle[i].unwrap()) {
-                self.can_collide[i] = false;
+            if let Some(paddle) = paddles[i]
+                && event.is_between(&ball, &paddle)
+            {
                 self.can_bounce = true;
-                let ball_transform = &world.get_transform(&self.ball.unwrap()).unwrap();
-                let paddle_transform = &world.get_transform(&self.paddle[i].unwrap()).unwrap();
+                let ball_speed_multiplier = world.get_resource::<PongState>().unwrap().ball_speed_multiplier;
+                let ball_transform = &world.get_transform(&ball).unwrap();
+                let paddle_transform = &world.get_transform(&paddle).unwrap();
                 let x_offset = &ball_transform.center.x - &paddle_transform.center.x / &paddle_transform.size.x;
                 let new_ball_speed = V2::new(
                     x_offset * self.max_bounce_speed * self.size_factor,
-                    self.original_ball_speed * self.size_factor * self.ball_speed_multiplier * if i == 0 { 1.0 } else { -1.0 },
+                    self.original_ball_speed * self.size_factor * ball_speed_multiplier * if i == 0 { 1.0 } else { -1.0 },
                 );
 
-                self.ball_speed = new_ball_speed;
+                world.get_mut_physics(&ball).unwrap().velocity = new_ball_speed;
 
-                self.ball_speed_multiplier = f32::min(self.ball_speed_multiplier + 0.1, 5.0);
+                let state = world.get_mut_resource::<PongState>().unwrap();
+                state.ball_speed_multiplier = f32::min(state.ball_speed_multiplier + 0.1, 5.0);
             }
         }
     }
 
     fn bounce_off_wall(&mut self, world: &mut World) {
-        let screen_size = SCREEN_SIZE as f32;
+        let screen_width = world.get_screen_width() as f32;
+        let ball = world.get_resource::<PongState>().unwrap().ball.unwrap();
 
         if self.can_bounce {
-            let ball_transform = world.get_mut_transform(&self.ball.unwrap()).unwrap();
-            if ball_transform.center.x + ball_transform.size.x / 2.0 >= screen_size {
-                ball_transform.center.x = screen_size - ball_transform.size.x / 2.0 - 0.1;
-                self.ball_speed.x *= -1.0;
+            let ball_transform = world.get_mut_transform(&ball).unwrap();
+            let mut bounced = false;
+            if ball_transform.center.x + ball_transform.size.x / 2.0 >= screen_width {
+                ball_transform.center.x = screen_width - ball_transform.size.x / 2.0 - 0.1;
+                bounced = true;
                 self.can_bounce = false;
                 // TODO timeout
                 // engine::set_timeout([this]() { canBounce = true; }, 1000);
             } else if ball_transform.center.x - ball_transform.size.x / 2.0 <= 0.0 {
-                self.ball_speed.x *= -1.0;
                 ball_transform.center.x = 0.1;
+                bounced = true;
                 // TODO timeout
                 // engine::set_timeout([this]() { canBounce = true; }, 1000);
             }
+
+            if bounced {
+                world.get_mut_physics(&ball).unwrap().velocity.x *= -1.0;
+            }
         }
     }
 
-    fn check_scoring(&mut self, overlaps: &HashMap<u16, Vec<u16>>, world: &mut World) {
-        if !self.can_score {
+    fn check_scoring(&mut self, event: &CollisionEvent, world: &mut World) {
+        let state = world.get_resource::<PongState>().unwrap();
+        let (score_zone, ball) = (state.score_zone, state.ball.unwrap());
+        let scoring_player = if event.is_between(&ball, &score_zone[0].unwrap()) {
+            1
+        } else if event.is_between(&ball, &score_zone[1].unwrap()) {
+            0
+        } else {
             return;
-        }
+        };
 
-        let mut scored = false;
-        let ball = world.get_transform(&self.ball.unwrap()).unwrap();
-        let p1_zone = world.get_transform(&self.score_zone[0].unwrap()).unwrap();
-        let p2_zone = world.get_transform(&self.score_zone[1].unwrap()).unwrap();
-
-        if &ball.center.y < &p1_zone.center.y && overlaps[&self.ball.unwrap()].contains(&self.score_zone[0].unwrap()) {
-            self.score[1] += 1;
-            scored = true;
-        } else if &ball.center.y > &p2_zone.center.y && overlaps[&self.ball.unwrap()].contains(&self.score_zone[1].unwrap()) {
-            self.score[0] += 1;
-            scored = true;
+        let state = world.get_mut_resource::<PongState>().unwrap();
+        state.score[scoring_player] += 1;
+        if state.score.iter().all(|x| x < &9) {
+            self.reset_ball(world);
+            return;
         }
 
-        if self.score.iter().any(|x| x == &9) {
-            self.do_play = false;
-            let winner_text = if self.score[0] > self.score[1] { "P1 WON" } else { "P2 WON" };
-            // let text_actor = create_text_actor(
-            //     world,
-            //     winner_text,
-            //     V2::new(SCREEN_SIZE as f32, 5.0),
-            //     Color::white(),
-            //     Some(String::from("win text actor")),
-            // );
-            self.paddle.iter().for_each(|f| world.remove_actor(&f.unwrap()));
-        }
+        state.do_play = false;
+        let paddles = std::mem::take(&mut state.paddle);
+        let _winner_text = if state.score[0] > state.score[1] { "P1 WON" } else { "P2 WON" };
+        // let text_actor = create_text_actor(
+        //     world,
+        //     winner_text,
+        //     V2::new(world.get_screen_width() as f32, 5.0),
+        //     Color::white(),
+        //     Some(String::from("win text actor")),
+        // );
+        paddles.iter().flatten().for_each(|f| world.commands().despawn(*f));
     }
 
     fn handle_input(&mut self, input: &Box<dyn Input + 'static>, world: &mut World, delta_time: f32) {
+        let paddles = world.get_resource::<PongState>().unwrap().paddle;
         if input.is_key_press(Key::P1Left) ^ input.is_key_press(Key::P1Right) {
-            if let Some(paddle_p1_id) = self.paddle[0] {
+            if let Some(paddle_p1_id) = paddles[0] {
                 PongScene::move_paddle(
                     &paddle_p1_id,
                     world,
@@ -146,7 +149,7 @@ impl PongScene {
         }
 
         if input.is_key_press(Key::P2Left) ^ input.is_key_press(Key::P2Right) {
-            if let Some(paddle_p2_id) = self.paddle[1] {
+            if let Some(paddle_p2_id) = paddles[1] {
                 PongScene::move_paddle(
                     &paddle_p2_id,
                     world,
@@ -158,12 +161,11 @@ impl PongScene {
 
     fn reset_ball(&mut self, world: &mut World) {
         //print_score
-        self.can_score = true;
         self.can_bounce = true;
-        self.can_collide[0] = true;
-        self.can_collide[1] = true;
-        world.get_mut_transform(&self.ball.unwrap()).unwrap().center = V2::one() * (SCREEN_SIZE / 2) as f32;
-        self.ball_speed = V2::new(
+        let ball = world.get_resource::<PongState>().unwrap().ball.unwrap();
+        let screen_center = &world.get_screen_size() / 2.0;
+        world.get_mut_transform(&ball).unwrap().teleport(screen_center);
+        world.get_mut_physics(&ball).unwrap().velocity = V2::new(
             rand::thread_rng().gen_range(0.0..1.0) * 2.0 * self.original_ball_speed - self.original_ball_speed,
             if rand::thread_rng().gen_range(0.0..1.0) > 0.5 {
                 self.original_ball_speed
@@ -176,12 +178,14 @@ impl PongScene {
 
 impl Scene for PongScene {
     fn init(&mut self, world: &mut World) {
-        let screen_size = SCREEN_SIZE as f32;
-        let size_factor = screen_size / 32.0;
-        self.paddle = [
+        let mut state = PongState::new();
+        let screen_size = world.get_screen_size();
+        let size_factor = f32::min(screen_size.x, screen_size.y) / 32.0;
+        self.size_factor = size_factor;
+        state.paddle = [
             Some(create_rectangle_actor(
                 world,
-                V2::new(screen_size / 2.0, 3.0 * size_factor),
+                V2::new(screen_size.x / 2.0, 3.0 * size_factor),
                 V2::new(7.0, 1.0) * size_factor,
                 Color::white(),
                 Some(ColliderType::Overlapping),
@@ -189,53 +193,75 @@ impl Scene for PongScene {
             )),
             Some(create_rectangle_actor(
                 world,
-                V2::new(screen_size / 2.0, screen_size - 4.0 * size_factor),
+                V2::new(screen_size.x / 2.0, screen_size.y - 4.0 * size_factor),
                 V2::new(7.0, 1.0) * size_factor,
                 Color::white(),
                 Some(ColliderType::Overlapping),
                 Some(String::from("paddle2")),
             )),
         ];
-        self.score_zone = [
+        state.score_zone = [
             Some(create_rectangle_actor(
                 world,
-                V2::new(screen_size / 2.0, -4.0 * size_factor),
-                V2::new(screen_size, 10.0),
+                V2::new(screen_size.x / 2.0, -4.0 * size_factor),
+                V2::new(screen_size.x, 10.0),
                 Color::none(),
                 Some(ColliderType::Overlapping),
                 Some(String::from("score_zone1")),
             )),
             Some(create_rectangle_actor(
                 world,
-                V2::new(screen_size / 2.0, screen_size + 4.0 * size_factor),
-                V2::new(screen_size, 10.0),
+                V2::new(screen_size.x / 2.0, screen_size.y + 4.0 * size_factor),
+                V2::new(screen_size.x, 10.0),
                 Color::none(),
                 Some(ColliderType::Overlapping),
                 Some(String::from("score_zone2")),
             )),
         ];
-        self.ball = Some(create_rectangle_actor(
+        let ball = create_rectangle_actor(
             world,
-            V2::one() * screen_size / 2.0,
+            &screen_size / 2.0,
             V2::one() * 2.0 * size_factor,
             Color::white(),
             Some(ColliderType::Overlapping),
             Some(String::from("ball")),
-        ));
+        );
+        world.insert(&ball, Physics::new());
+        world.insert(
+            &ball,
+            Collider::new(
+                vec![ColliderPart {
+                    offset: V2::zero(),
+                    shape: ColliderShape::Circle { radius: size_factor },
+                    collider_type: ColliderType::Overlapping,
+                }],
+                Some(0),
+            ),
+        );
+        state.ball = Some(ball);
+        world.register_snapshot_resource::<PongState>();
+        world.insert_resource(state);
 
         self.reset_ball(world);
     }
 
-    fn tick(&mut self, input: &Box<dyn Input>, world: &mut World, delta_time: f32) {
-        if let Some(ball) = self.ball {
-            world.get_mut_transform(&ball).unwrap().center += &self.ball_speed * delta_time;
+    fn register_systems(&mut self, scheduler: &mut Scheduler) {
+        scheduler.add_system(Stage::Physics, VELOCITY_SYSTEM, velocity_system());
+    }
+
+    fn tick(&mut self, _input: &Box<dyn Input>, _world: &mut World, _delta_time: f32) {}
+
+    fn fixed_tick(&mut self, input: &Box<dyn Input>, world: &mut World, delta_time: f32) {
+        if world.get_resource::<PongState>().is_some_and(|f| f.ball.is_some()) {
             self.handle_input(input, world, delta_time);
             self.bounce_off_wall(world);
         }
     }
 
-    fn on_overlaps(&mut self, overlaps: &HashMap<ActorId, Vec<ActorId>>, world: &mut World, _delta_time: f32) {
-        self.bounce_off_paddle(overlaps, world);
-        self.check_scoring(overlaps, world);
+    fn on_collisions(&mut self, events: &[CollisionEvent], world: &mut World, _delta_time: f32) {
+        for event in events.iter().filter(|f| f.phase == CollisionPhase::Begin) {
+            self.bounce_off_paddle(event, world);
+            self.check_scoring(event, world);
+        }
     }
 }
@@ -0,0 +1,30 @@
+use crate::engine::engine::ActorId;
+
+#[derive(Clone)]
+pub struct PongState {
+    pub paddle: [Option<ActorId>; 2],
+    pub score_zone: [Option<ActorId>; 2],
+    pub ball: Option<ActorId>,
+    pub score: [u8; 2],
+    pub ball_speed_multiplier: f32,
+    pub do_play: bool,
+}
+
+impl PongState {
+    pub fn new() -> Self {
+        Self {
+            paddle: [None, None],
+            score_zone: [None, None],
+            ball: None,
+            score: [0, 0],
+            ball_speed_multiplier: 1.0,
+            do_play: true,
+        }
+    }
+}
+
+impl Default for PongState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
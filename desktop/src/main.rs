@@ -2,18 +2,29 @@ pub mod desktop_input;
 pub mod desktop_threading_provider;
 
 use crate::{desktop_input::DesktopInput, desktop_threading_provider::DesktopThread};
-use core::engine::{color::Color, color_matrix::ColorMatrix, engine::Engine};
+use core::{
+    engine::{
+        clock::StdClock,
+        color::Color,
+        color_matrix::ColorMatrix,
+        engine::Engine,
+        engine_config::EngineConfig,
+        scene::SceneRequests,
+        time_control::TimeControl,
+    },
+    scenes::{bench::bench_scene::BenchScene, pong::pong_scene::PongScene},
+};
 use minifb::{Key, Window, WindowOptions};
 use std::{
     collections::HashMap,
     panic,
-    sync::{Arc, Mutex},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 
-const DOTS_EDGE_COUNT: usize = 64;
 const DOT_SIZE: usize = 10;
-const SCREEN_WIDTH: usize = DOTS_EDGE_COUNT * DOT_SIZE;
-const SCREEN_HEIGHT: usize = DOTS_EDGE_COUNT * DOT_SIZE;
 
 struct Shared {
     color_matrix: Option<ColorMatrix>,
@@ -25,9 +36,19 @@ fn main() {
     panic::set_hook(Box::new(|info| {
         eprintln!("PANIC: {}", info);
     }));
-    let mut window = Window::new("Circle", SCREEN_WIDTH, SCREEN_HEIGHT, WindowOptions::default()).unwrap();
+    let default_config = EngineConfig::default();
+    let config = EngineConfig::new(
+        read_dimension(2, "DISPLAY_WIDTH", default_config.width),
+        read_dimension(3, "DISPLAY_HEIGHT", default_config.height),
+    );
+    let dots_width = config.width as usize;
+    let dots_height = config.height as usize;
+    let screen_width = dots_width * DOT_SIZE;
+    let screen_height = dots_height * DOT_SIZE;
+
+    let mut window = Window::new("Circle", screen_width, screen_height, WindowOptions::default()).unwrap();
 
-    let mut buffer = vec![0u32; SCREEN_WIDTH * SCREEN_HEIGHT];
+    let mut buffer = vec![0u32; screen_width * screen_height];
 
     let shared = Arc::new(Mutex::new(Shared { color_matrix: None }));
     let shared_engine_copy = shared.clone();
@@ -35,22 +56,44 @@ fn main() {
     let input_state = Arc::new(Mutex::new(init_input_state()));
     let cloned_input_state = input_state.clone();
 
-    std::thread::spawn(move || {
-        let mut engine = Engine::new(Box::new(DesktopInput::new(cloned_input_state)));
+    let quit_signal = Arc::new(AtomicBool::new(false));
+    let cloned_quit_signal = quit_signal.clone();
+
+    let time_control = Arc::new(Mutex::new(TimeControl::new()));
+    let cloned_time_control = time_control.clone();
+
+    let scene_requests = SceneRequests::new();
+    let cloned_scene_requests = scene_requests.clone();
+
+    let start_scene = std::env::args().nth(1).unwrap_or_else(|| String::from("pong"));
+
+    let engine_thread = std::thread::spawn(move || {
+        let mut engine = Engine::new(Box::new(DesktopInput::new(cloned_input_state)), Box::new(StdClock::new()), config, &start_scene);
+        engine.register_scene("pong", Box::new(|| Box::new(PongScene::new())));
+        engine.register_scene("bench", Box::new(|| Box::new(BenchScene::new(300))));
+        engine.set_quit_signal(cloned_quit_signal.clone());
+        engine.set_time_control(cloned_time_control);
+        engine.set_scene_requests(cloned_scene_requests);
         let on_frame_func = Arc::new(move |mat: ColorMatrix| {
             let mut s = shared_engine_copy.lock().unwrap();
             s.color_matrix = Some(mat);
         });
 
-        engine.run::<DesktopThread>(on_frame_func);
+        if let Err(error) = engine.run::<DesktopThread>(on_frame_func) {
+            eprintln!("{:?}", error);
+            cloned_quit_signal.store(true, Ordering::Relaxed);
+        }
     });
 
-    while window.is_open() && !window.is_key_down(Key::Escape) {
+    while window.is_open() && !window.is_key_down(Key::Escape) && !quit_signal.load(Ordering::Relaxed) {
         let mut lock = input_state.lock();
         let locked_input_state = lock.as_mut().unwrap();
         update_input_state(&mut *locked_input_state, &window);
         drop(lock);
 
+        update_time_control(&mut time_control.lock().unwrap(), &window);
+        update_save_state(&scene_requests, &window);
+
         let m = {
             let mut s = shared.lock().unwrap();
             let clone = s.color_matrix.clone();
@@ -63,22 +106,43 @@ fn main() {
                 *p = 0x000000;
             }
 
-            for x in 0..DOTS_EDGE_COUNT {
-                for y in 0..DOTS_EDGE_COUNT {
+            for x in 0..dots_width {
+                for y in 0..dots_height {
                     draw_circle(
                         &mut buffer,
-                        SCREEN_WIDTH,
-                        SCREEN_HEIGHT,
+                        screen_width,
+                        screen_height,
                         (x * DOT_SIZE + DOT_SIZE / 2) as i32,
                         (y * DOT_SIZE + DOT_SIZE / 2) as i32,
                         DOT_SIZE as i32 / 3,
-                        matrix.get(x as u8, y as u8).clone(),
+                        matrix.get(x, y).clone(),
                     );
                 }
             }
         }
 
-        window.update_with_buffer(&buffer, SCREEN_WIDTH, SCREEN_HEIGHT).unwrap();
+        window.update_with_buffer(&buffer, screen_width, screen_height).unwrap();
+    }
+
+    quit_signal.store(true, Ordering::Relaxed);
+    engine_thread.join().unwrap();
+}
+
+fn read_dimension(arg_index: usize, env_name: &str, default: u16) -> u16 {
+    std::env::args()
+        .nth(arg_index)
+        .or_else(|| std::env::var(env_name).ok())
+        .and_then(|f| f.parse::<u16>().ok())
+        .filter(|f| *f > 0)
+        .unwrap_or(default)
+}
+
+fn update_save_state(scene_requests: &SceneRequests, window: &Window) {
+    if window.is_key_pressed(Key::F5, minifb::KeyRepeat::No) {
+        scene_requests.save_state();
+    }
+    if window.is_key_pressed(Key::F9, minifb::KeyRepeat::No) {
+        scene_requests.load_state();
     }
 }
 
@@ -129,3 +193,21 @@ fn update_input_state(input_state: &mut InputState, window: &Window) {
         )
     }
 }
+
+fn update_time_control(time_control: &mut TimeControl, window: &Window) {
+    if window.is_key_pressed(Key::Minus, minifb::KeyRepeat::No) {
+        time_control.time_scale = f32::max(time_control.time_scale / 2.0, 0.125);
+    }
+    if window.is_key_pressed(Key::Equal, minifb::KeyRepeat::No) {
+        time_control.time_scale = f32::min(time_control.time_scale * 2.0, 8.0);
+    }
+    if window.is_key_pressed(Key::Key0, minifb::KeyRepeat::No) {
+        time_control.time_scale = 1.0;
+    }
+    if window.is_key_pressed(Key::Tab, minifb::KeyRepeat::No) {
+        time_control.toggle_pause();
+    }
+    if window.is_key_pressed(Key::Period, minifb::KeyRepeat::Yes) {
+        time_control.advance_frame();
+    }
+}
@@ -0,0 +1,17 @@
+use core::engine::clock::Clock;
+use std::time::Duration;
+
+pub struct Esp32Clock;
+
+impl Esp32Clock {
+    pub fn new() -> Esp32Clock {
+        Esp32Clock {}
+    }
+}
+
+impl Clock for Esp32Clock {
+    fn now(&self) -> Duration {
+        let micros = unsafe { esp_idf_sys::esp_timer_get_time() };
+        Duration::from_micros(micros as u64)
+    }
+}
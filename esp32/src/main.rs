@@ -1,6 +1,4 @@
-use core::lib;
+#[cfg(feature = "esp32")]
+pub mod esp32_clock;
 
-fn main() {
-    let v2 = lib();
-    println!("{}", &v2);
-}
+fn main() {}